@@ -0,0 +1,142 @@
+use aoc_common::Timings;
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Changes smaller than this are timer noise, whatever the percentage
+const NOISE_FLOOR: Duration = Duration::from_micros(100);
+
+/// One day's timings, in nanoseconds so the report survives a round trip
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse_ns: u64,
+    pub part_a_ns: u64,
+    pub part_b_ns: u64,
+}
+
+impl DayReport {
+    pub fn new(day: u8, timings: &Timings) -> Self {
+        let ns = |d: Duration| d.as_nanos() as u64;
+        Self { day, parse_ns: ns(timings.parse),
+            part_a_ns: ns(timings.part_a), part_b_ns: ns(timings.part_b) }
+    }
+
+    fn stages(&self) -> [(&'static str, Duration); 3] {
+        [("parse", Duration::from_nanos(self.parse_ns)),
+            ("part a", Duration::from_nanos(self.part_a_ns)),
+            ("part b", Duration::from_nanos(self.part_b_ns))]
+    }
+}
+
+/// A whole benchmark run, which is also the format of saved baselines
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub input: String,
+    pub repeats: usize,
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json() + "\n")
+            .map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Reports always serialize")
+    }
+
+    /// Fails unless `baseline` timed the same input with the same number
+    /// of repeats, without which its times aren't comparable with these
+    pub fn check_comparable(&self, baseline: &Report) -> Result<(), String> {
+        if self.input != baseline.input || self.repeats != baseline.repeats {
+            return Err(format!(
+                "the baseline timed the {} input with {} repeats, but this run times the {} input with {}",
+                baseline.input, baseline.repeats, self.input, self.repeats));
+        }
+        Ok(())
+    }
+
+    /// Every stage whose time moved by more than `threshold` percent
+    /// relative to `baseline`. Days missing from either side are skipped.
+    pub fn compare(&self, baseline: &Report, threshold: f64) -> Result<Vec<Change>, String> {
+        self.check_comparable(baseline)?;
+        let mut ret = Vec::new();
+        for day in &self.days {
+            let old = match baseline.days.iter().find(|d| d.day == day.day) {
+                Some(d) => d,
+                None => continue,
+            };
+            for ((stage, now), (_, before)) in day.stages().into_iter().zip(old.stages()) {
+                let delta = now.abs_diff(before);
+                let percent = 100.0 * (now.as_secs_f64() / before.as_secs_f64() - 1.0);
+                if delta > NOISE_FLOOR && percent.abs() > threshold {
+                    ret.push(Change { day: day.day, stage, before, now, percent });
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos() as f64;
+    if ns < 1e3 {
+        format!("{:.0}ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.1}µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.1}ms", ns / 1e6)
+    } else {
+        format!("{:.2}s", ns / 1e9)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>3} {:>10} {:>10} {:>10} {:>10}", "Day", "parse", "part a", "part b", "total")?;
+        for day in &self.days {
+            let stages = day.stages();
+            let total = stages.iter().map(|(_, d)| *d).sum();
+            writeln!(f, "{:>3} {:>10} {:>10} {:>10} {:>10}", day.day,
+                format_duration(stages[0].1), format_duration(stages[1].1),
+                format_duration(stages[2].1), format_duration(total))?;
+        }
+        Ok(())
+    }
+}
+
+/// A stage that got notably faster or slower than its baseline
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub stage: &'static str,
+    pub before: Duration,
+    pub now: Duration,
+    pub percent: f64,
+}
+
+impl Change {
+    pub fn is_regression(&self) -> bool {
+        self.now > self.before
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} {}: {} -> {} ({:+.0}%) {}", self.day, self.stage,
+            format_duration(self.before), format_duration(self.now), self.percent,
+            if self.is_regression() { "REGRESSION" } else { "faster" })
+    }
+}
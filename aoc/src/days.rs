@@ -0,0 +1,71 @@
+use aoc_common::{Answers, Part, Result, Solution, Timings};
+
+/// Everything the runner needs to know about one day's solution
+pub struct Day {
+    pub number: u8,
+    pub puzzle: &'static str,
+    pub sample: &'static str,
+    pub solve: fn(&str, &[Part]) -> Result<Answers>,
+    pub time: fn(&str, usize) -> Result<Timings>,
+}
+
+impl Day {
+    /// Whether the puzzle input is embedded, rather than left empty
+    pub fn has_puzzle(&self) -> bool {
+        !self.puzzle.is_empty()
+    }
+}
+
+macro_rules! day {
+    ($krate:ident :: $solution:ident) => {
+        Day {
+            number: <$krate::$solution as Solution>::DAY,
+            puzzle: $krate::PUZZLE,
+            sample: $krate::SAMPLE,
+            solve: aoc_common::solve::<$krate::$solution>,
+            time: aoc_common::timing::time::<$krate::$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(day01::Day01),
+    day!(day02::Day02),
+    day!(day03::Day03),
+    day!(day04::Day04),
+    day!(day05::Day05),
+    day!(day06::Day06),
+    day!(day07::Day07),
+    day!(day08::Day08),
+    day!(day09::Day09),
+    day!(day10::Day10),
+    day!(day11::Day11),
+    day!(day12::Day12),
+    day!(day13::Day13),
+    day!(day14::Day14),
+    day!(day15::Day15),
+    day!(day16::Day16),
+    day!(day17::Day17),
+    day!(day18::Day18),
+    day!(day19::Day19),
+    day!(day20::Day20),
+    day!(day21::Day21),
+    day!(day22::Day22),
+    day!(day23::Day23),
+    day!(day24::Day24),
+    day!(day25::Day25),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/// The days named on the command line, or every day if none were
+pub fn select(numbers: &[u8]) -> std::result::Result<Vec<&'static Day>, String> {
+    if numbers.is_empty() {
+        return Ok(DAYS.iter().collect());
+    }
+    numbers.iter().copied()
+        .map(|n| find(n).ok_or_else(|| format!("No solution for day {}", n)))
+        .collect()
+}
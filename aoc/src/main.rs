@@ -0,0 +1,253 @@
+mod bench;
+mod days;
+mod output;
+mod pool;
+mod verify;
+
+use aoc_common::{input::Source, logger, print_answer, Part};
+use bench::{DayReport, Report};
+use output::{DayOutput, RunOutput};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use verify::{Input, KnownAnswers, Tally};
+
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs Advent of Code 2021 solutions")]
+struct Cli {
+    /// Log more on stderr: -v for info, -vv for debug, -vvv for trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve some or all of the days
+    Run(RunArgs),
+    /// Check answers against the known-answer store
+    Verify(VerifyArgs),
+    /// Time parsing and each part, optionally against a saved baseline
+    Bench(BenchArgs),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve, may be repeated (default: every day)
+    #[arg(short, long = "day")]
+    days: Vec<u8>,
+    /// Part to solve (default: both)
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Use the sample input from the problem statement
+    #[arg(short, long, conflicts_with = "input")]
+    sample: bool,
+    /// Read the input from this file, or stdin for `-`. Needs a single --day
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Print answers as text, or as one JSON document with timings
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Solve this many days at once, or 0 for one per CPU. Output stays in day order
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to check, may be repeated (default: every day)
+    #[arg(short, long = "day")]
+    days: Vec<u8>,
+    /// Part to check (default: both)
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Only check the sample inputs
+    #[arg(short, long, conflicts_with = "puzzle")]
+    sample: bool,
+    /// Only check the puzzle inputs
+    #[arg(short = 'P', long)]
+    puzzle: bool,
+    /// JSON file of known answers
+    #[arg(long, default_value = verify::DEFAULT_STORE)]
+    answers: PathBuf,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to time, may be repeated (default: every day)
+    #[arg(short, long = "day")]
+    days: Vec<u8>,
+    /// Time the sample inputs instead of the puzzle inputs
+    #[arg(short, long)]
+    sample: bool,
+    /// How many times to run each stage; the median is reported
+    #[arg(short, long, default_value_t = 5)]
+    repeats: usize,
+    /// Print a table, or the JSON report that --save writes
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Save the report here, for use as a later --baseline
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// Compare against a report saved with --save
+    #[arg(short, long)]
+    baseline: Option<PathBuf>,
+    /// Percent change from the baseline worth reporting
+    #[arg(short, long, default_value_t = 25.0)]
+    threshold: f64,
+}
+
+impl RunArgs {
+    fn source(&self) -> Source {
+        match &self.input {
+            Some(path) => Source::from_path(path),
+            None if self.sample => Source::Sample,
+            None => Source::Puzzle,
+        }
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(p) => vec![p],
+            None => Part::BOTH.to_vec(),
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> Result<bool, String> {
+    let days = days::select(&args.days)?;
+    if args.input.is_some() && days.len() != 1 {
+        return Err("--input needs exactly one --day".into());
+    }
+    let source = args.source();
+    let days: Vec<_> = days.into_iter().filter(|day| {
+        let skip = source == Source::Puzzle && !day.has_puzzle() && args.days.is_empty();
+        if skip {
+            eprintln!("Day {}: no embedded puzzle input, skipped", day.number);
+        }
+        !skip
+    }).collect();
+    let parts = args.parts();
+    let results = pool::map(&days, args.jobs, |day| {
+        source.read(day.puzzle, day.sample)
+            .map_err(|e| io::Error::new(e.kind(), format!("couldn't read {}: {}", source, e)).into())
+            .and_then(|input_str| (day.solve)(&input_str, &parts))
+    });
+    let mut all_ok = true;
+    let mut output = RunOutput { input: source.to_string(), days: vec![] };
+    for (day, result) in days.iter().zip(results) {
+        // A panic in one day is reported like any other failure
+        let result = result.unwrap_or_else(|msg|
+            Err(aoc_common::Error::solve(day.number, format!("panicked: {}", msg))));
+        all_ok &= result.is_ok();
+        match args.format {
+            Format::Text => {
+                println!("Day {}", day.number);
+                match result {
+                    Ok(answers) => answers.parts.iter().for_each(print_answer),
+                    Err(e) => eprintln!("error: {}", e),
+                }
+            },
+            Format::Json => output.days.push(DayOutput::new(day.number, &result)),
+        }
+    }
+    if args.format == Format::Json {
+        println!("{}", output.to_json());
+    }
+    Ok(all_ok)
+}
+
+fn verify(args: &VerifyArgs) -> Result<bool, String> {
+    let days = days::select(&args.days)?;
+    let known = KnownAnswers::load(&args.answers)?;
+    let inputs: &[Input] = if args.sample {
+        &[Input::Sample]
+    } else if args.puzzle {
+        &[Input::Puzzle]
+    } else {
+        &Input::BOTH
+    };
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => Part::BOTH.to_vec(),
+    };
+    let mut tally = Tally::default();
+    for day in days {
+        for input in inputs.iter().copied() {
+            verify::verify_day(day, input, &parts, &known, &mut tally);
+        }
+    }
+    println!("{}", tally);
+    Ok(tally.failed == 0)
+}
+
+fn bench(args: &BenchArgs) -> Result<bool, String> {
+    let days = days::select(&args.days)?;
+    let baseline = args.baseline.as_deref().map(Report::load).transpose()?;
+    let input = if args.sample { Input::Sample } else { Input::Puzzle };
+    let mut report = Report { input: input.to_string(), repeats: args.repeats, days: vec![] };
+    // Checked before timing anything, rather than after a run that can't be compared
+    if let Some(baseline) = &baseline {
+        report.check_comparable(baseline)?;
+    }
+    let mut all_ok = true;
+    for day in days {
+        if input == Input::Puzzle && !day.has_puzzle() && args.days.is_empty() {
+            eprintln!("Day {}: no embedded puzzle input, skipped", day.number);
+            continue;
+        }
+        let timings = input.source().read(day.puzzle, day.sample)
+            .map_err(aoc_common::Error::from)
+            .and_then(|text| (day.time)(&text, args.repeats));
+        match timings {
+            Ok(t) => report.days.push(DayReport::new(day.number, &t)),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                all_ok = false;
+            }
+        }
+    }
+    match args.format {
+        Format::Text => print!("{}", report),
+        Format::Json => println!("{}", report.to_json()),
+    }
+    if let Some(path) = &args.save {
+        report.save(path)?;
+    }
+    if let Some(baseline) = baseline {
+        for change in report.compare(&baseline, args.threshold)? {
+            if change.is_regression() {
+                all_ok = false;
+            }
+            eprintln!("{}", change);
+        }
+    }
+    Ok(all_ok)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logger::init(logger::level_for(cli.verbose));
+    let result = match &cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
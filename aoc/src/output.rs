@@ -0,0 +1,56 @@
+use aoc_common::{Answers, PartAnswer};
+use serde::Serialize;
+
+/// One part's answer as it appears in `--format json` output
+#[derive(Clone, Debug, Serialize)]
+pub struct PartOutput {
+    pub part: String,
+    pub answer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aux: Option<String>,
+    pub time_ns: u64,
+}
+
+impl From<&PartAnswer> for PartOutput {
+    fn from(answer: &PartAnswer) -> Self {
+        Self { part: answer.part.to_string(), answer: answer.answer.clone(),
+            aux: answer.aux.clone(), time_ns: answer.time.as_nanos() as u64 }
+    }
+}
+
+/// A day's answers, or the error that stopped it
+#[derive(Clone, Debug, Serialize)]
+pub struct DayOutput {
+    pub day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<PartOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DayOutput {
+    pub fn new(day: u8, result: &aoc_common::Result<Answers>) -> Self {
+        match result {
+            Ok(answers) => Self { day,
+                parse_ns: Some(answers.parse_time.as_nanos() as u64),
+                parts: answers.parts.iter().map(PartOutput::from).collect(),
+                error: None },
+            Err(e) => Self { day, parse_ns: None, parts: vec![], error: Some(e.to_string()) },
+        }
+    }
+}
+
+/// Everything `run --format json` prints, as one document
+#[derive(Clone, Debug, Serialize)]
+pub struct RunOutput {
+    pub input: String,
+    pub days: Vec<DayOutput>,
+}
+
+impl RunOutput {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Output always serializes")
+    }
+}
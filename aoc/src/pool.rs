@@ -0,0 +1,48 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// How many threads to use when asked for `jobs`, where 0 means one per CPU
+pub fn threads(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+/// The message a panic was started with, if it's a string
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload.downcast_ref::<&str>().copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Calls `f` on every item using up to `jobs` threads, which take the
+/// next unstarted item whenever they finish one. The results come back
+/// in the order of `items`, and an item whose call panicked gets the
+/// panic's message instead of stopping the rest.
+pub fn map<T: Sync, R: Send>(items: &[T], jobs: usize,
+        f: impl Fn(&T) -> R + Sync) -> Vec<Result<R, String>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<R, String>>>> =
+        Mutex::new((0..items.len()).map(|_| None).collect());
+    let work = || loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let item = match items.get(i) {
+            Some(item) => item,
+            None => break,
+        };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)))
+            .map_err(|payload| panic_message(payload.as_ref()).to_string());
+        results.lock().expect("Workers don't panic while holding the lock")[i] = Some(result);
+    };
+    thread::scope(|s| {
+        for _ in 0..threads(jobs).min(items.len()) {
+            s.spawn(work);
+        }
+    });
+    results.into_inner().expect("Workers don't panic while holding the lock")
+        .into_iter().map(|r| r.expect("Every item was taken by a worker")).collect()
+}
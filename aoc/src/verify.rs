@@ -0,0 +1,179 @@
+use crate::days::Day;
+use aoc_common::{input::Source, Part};
+use serde::Deserialize;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The store that ships with the runner, next to its manifest
+pub const DEFAULT_STORE: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.json");
+
+/// Which embedded input an answer belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    Sample,
+    Puzzle,
+}
+
+impl Input {
+    pub const BOTH: [Input; 2] = [Input::Sample, Input::Puzzle];
+
+    pub fn source(self) -> Source {
+        match self {
+            Input::Sample => Source::Sample,
+            Input::Puzzle => Source::Puzzle,
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Sample => write!(f, "sample"),
+            Input::Puzzle => write!(f, "puzzle"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct PartAnswers {
+    a: Option<String>,
+    b: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct DayAnswers {
+    #[serde(default)]
+    sample: PartAnswers,
+    #[serde(default)]
+    puzzle: PartAnswers,
+}
+
+/// Expected answers, keyed by day, then input, then part.
+/// Multi-line answers are stored with embedded newlines.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct KnownAnswers(BTreeMap<u8, DayAnswers>);
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u8, input: Input, part: Part) -> Option<&str> {
+        let day = self.0.get(&day)?;
+        let parts = match input {
+            Input::Sample => &day.sample,
+            Input::Puzzle => &day.puzzle,
+        };
+        match part {
+            Part::A => parts.a.as_deref(),
+            Part::B => parts.b.as_deref(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, got: String },
+    Missing { got: String },
+}
+
+impl Verdict {
+    pub fn judge(expected: Option<&str>, got: String) -> Self {
+        match expected {
+            Some(e) if e == got => Verdict::Pass,
+            Some(e) => Verdict::Fail { expected: e.to_owned(), got },
+            None => Verdict::Missing { got },
+        }
+    }
+}
+
+// Multi-line answers start on their own line so they stay aligned
+fn show(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}\n", answer)
+    } else {
+        answer.to_owned()
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected, got } =>
+                write!(f, "FAIL (expected {}, got {})", show(expected), show(got)),
+            Verdict::Missing { got } => write!(f, "MISSING (got {})", show(got)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Tally {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+    /// Days whose puzzle answers couldn't be checked, since there's no input
+    pub unverified: Vec<u8>,
+}
+
+impl Tally {
+    fn count(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail { .. } => self.failed += 1,
+            Verdict::Missing { .. } => self.missing += 1,
+        }
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} passed, {} failed, {} missing", self.passed, self.failed, self.missing)?;
+        if !self.unverified.is_empty() {
+            let days: Vec<String> = self.unverified.iter().map(u8::to_string).collect();
+            write!(f, "\nUnverified, with no embedded puzzle input: days {}", days.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves `day` on `input` and prints a verdict for every part in `parts`.
+/// A day that fails to solve counts as a failure of each part,
+/// and a puzzle input that isn't embedded leaves the day unverified.
+pub fn verify_day(day: &Day, input: Input, parts: &[Part],
+        known: &KnownAnswers, tally: &mut Tally) {
+    let label = |part| format!("Day {} {} {}", day.number, input, part);
+    if input == Input::Puzzle && !day.has_puzzle() {
+        for part in parts.iter().copied() {
+            println!("{}: UNVERIFIED (no embedded input)", label(part));
+        }
+        tally.unverified.push(day.number);
+        return;
+    }
+    let answers = input.source().read(day.puzzle, day.sample)
+        .map_err(aoc_common::Error::from)
+        .and_then(|text| (day.solve)(&text, parts));
+    match answers {
+        Ok(answers) => {
+            for answer in answers.parts {
+                let verdict = Verdict::judge(known.get(day.number, input, answer.part), answer.answer);
+                println!("{}: {}", label(answer.part), verdict);
+                tally.count(&verdict);
+            }
+        },
+        Err(e) => {
+            for part in parts.iter().copied() {
+                println!("{}: FAIL ({})", label(part), e);
+                tally.failed += 1;
+            }
+        }
+    }
+}
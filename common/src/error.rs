@@ -0,0 +1,99 @@
+use std::fmt;
+use std::io;
+
+/// A 1-based position in a day's input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Everything that can go wrong while solving a day
+#[derive(Debug)]
+pub enum Error {
+    /// The input is malformed
+    Parse { day: u8, at: Location, message: String },
+    /// The input parsed, but doesn't have an answer
+    Solve { day: u8, at: Option<Location>, message: String },
+    Io(io::Error),
+}
+
+impl Error {
+    pub fn parse(day: u8, line: usize, column: usize,
+            message: impl Into<String>) -> Self {
+        Error::Parse { day, at: Location { line, column },
+            message: message.into() }
+    }
+
+    pub fn solve(day: u8, message: impl Into<String>) -> Self {
+        Error::Solve { day, at: None, message: message.into() }
+    }
+
+    /// A failure to solve that can be blamed on a particular line
+    pub fn solve_at(day: u8, line: usize, message: impl Into<String>) -> Self {
+        Error::Solve { day, at: Some(Location { line, column: 1 }),
+            message: message.into() }
+    }
+
+    /// Moves the error `lines` lines down, for when the text that was
+    /// being parsed didn't start at the top of the input.
+    ///
+    /// Parsers for a single line, such as the days' `FromStr` impls,
+    /// report their errors on line 1. Callers that know where the line
+    /// came from offset them with `.map_err(|e: Error| e.offset_lines(i))`,
+    /// where `i` is the 0-based index of the line.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        match &mut self {
+            Error::Parse { at, .. } | Error::Solve { at: Some(at), .. } => {
+                at.line += lines;
+            },
+            Error::Solve { at: None, .. } | Error::Io(_) => (),
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { day, at, message } =>
+                write!(f, "day {}, {}: {}", day, at, message),
+            Error::Solve { day, at: Some(at), message } =>
+                write!(f, "day {}, {}: {}", day, at, message),
+            Error::Solve { day, at: None, message } =>
+                write!(f, "day {}: {}", day, message),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The 1-based column at which `token` starts within `line`.
+/// `token` must be a slice of `line`.
+pub fn column(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    debug_assert!(offset <= line.len(), "token is not part of line");
+    offset + 1
+}
@@ -0,0 +1,302 @@
+use fxhash::FxHashMap;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+/// Nodes are numbered from 0 in the order they're added
+pub type NodeId = usize;
+
+/// A directed edge and what it costs to follow it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub to: NodeId,
+    pub weight: u64,
+}
+
+/// A directed graph with weighted edges. Nodes can be given names,
+/// which are interned so that they're only stored once.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    edges: Vec<Vec<Edge>>,
+    ids: FxHashMap<String, NodeId>,
+    names: Vec<Option<String>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A graph of `n` unnamed nodes and no edges
+    pub fn with_nodes(n: usize) -> Self {
+        Self { edges: vec![vec![]; n], ..Self::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn add_node(&mut self) -> NodeId {
+        self.edges.push(vec![]);
+        self.edges.len() - 1
+    }
+
+    /// The node called `name`, which is added if there isn't one yet
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.add_node();
+        self.ids.insert(name.to_owned(), id);
+        // Unnamed nodes only take up space once a later node is named
+        self.names.resize(id + 1, None);
+        self.names[id] = Some(name.to_owned());
+        id
+    }
+
+    /// The node called `name`, if there is one
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> Option<&str> {
+        self.names.get(id)?.as_deref()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push(Edge { to, weight });
+    }
+
+    /// An edge with weight 1 in each direction
+    pub fn add_unweighted(&mut self, a: NodeId, b: NodeId) {
+        self.add_edge(a, b, 1);
+        self.add_edge(b, a, 1);
+    }
+
+    pub fn edges(&self, node: NodeId) -> &[Edge] {
+        &self.edges[node]
+    }
+
+    pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[node].iter().map(|e| e.to)
+    }
+
+    /// The cost of the cheapest path from `start` to every node,
+    /// or `None` where there's no path
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<u64>> {
+        self.dijkstra_until(start, None)
+    }
+
+    /// The cost of the cheapest path from `start` to `end`.
+    /// Stops searching once `end` is reached.
+    pub fn shortest_path(&self, start: NodeId, end: NodeId) -> Option<u64> {
+        self.dijkstra_until(start, Some(end))[end]
+    }
+
+    fn dijkstra_until(&self, start: NodeId, end: Option<NodeId>) -> Vec<Option<u64>> {
+        let mut queue = BinaryHeap::<Reverse<(u64, NodeId)>>::new();
+        let mut dists = vec![u64::MAX; self.len()];
+        let mut visited = vec![false; self.len()];
+        dists[start] = 0;
+        queue.push(Reverse((0, start)));
+        while let Some(Reverse((_, node))) = queue.pop() {
+            if visited[node] {
+                // Duplicate entry from distance update
+                continue;
+            }
+            visited[node] = true;
+            if Some(node) == end {
+                break;
+            }
+            let to_here = dists[node];
+            for edge in self.edges(node) {
+                if visited[edge.to] {
+                    continue;
+                }
+                let proposed = to_here + edge.weight;
+                if proposed < dists[edge.to] {
+                    dists[edge.to] = proposed;
+                    queue.push(Reverse((proposed, edge.to)));
+                }
+            }
+        }
+        dists.into_iter().map(|d| if d == u64::MAX { None } else { Some(d) }).collect()
+    }
+
+    /// The number of edges on the shortest path from `start` to every node,
+    /// ignoring weights, or `None` where there's no path
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut hops = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        hops[start] = Some(0);
+        queue.push_back(start);
+        while let Some(node) = queue.pop_front() {
+            let next = hops[node].map(|h| h + 1);
+            for neighbor in self.neighbors(node) {
+                if hops[neighbor].is_none() {
+                    hops[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        hops
+    }
+
+    /// The nodes reachable from `start`, in depth-first preorder
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut ret = vec![];
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            ret.push(node);
+            // Reversed so that the first neighbor is explored first
+            stack.extend(self.edges(node).iter().rev().map(|e| e.to).filter(|n| !seen[*n]));
+        }
+        ret
+    }
+
+    /// Calls `visit` on every path from `start` to `end`, which paths
+    /// don't continue past. The walk may only move into a node if `enter`
+    /// returns the state after doing so, given the state before. The walk
+    /// starts in the state `init`.
+    pub fn for_each_path<S>(&self, start: NodeId, end: NodeId, init: S,
+            enter: &mut impl FnMut(&S, NodeId) -> Option<S>,
+            visit: &mut impl FnMut(&[NodeId])) {
+        let mut path = vec![start];
+        self.extend_paths(end, &init, &mut path, enter, visit);
+    }
+
+    fn extend_paths<S>(&self, end: NodeId, state: &S, path: &mut Vec<NodeId>,
+            enter: &mut impl FnMut(&S, NodeId) -> Option<S>,
+            visit: &mut impl FnMut(&[NodeId])) {
+        let node = *path.last().expect("Paths are never empty");
+        if node == end {
+            visit(path);
+            return;
+        }
+        for neighbor in self.neighbors(node) {
+            if let Some(next) = enter(state, neighbor) {
+                path.push(neighbor);
+                self.extend_paths(end, &next, path, enter, visit);
+                path.pop();
+            }
+        }
+    }
+
+    /// The number of paths `for_each_path` would visit
+    pub fn count_paths<S>(&self, start: NodeId, end: NodeId, init: S,
+            mut enter: impl FnMut(&S, NodeId) -> Option<S>) -> usize {
+        let mut ret = 0;
+        self.for_each_path(start, end, init, &mut enter, &mut |_| ret += 1);
+        ret
+    }
+}
+
+/// Dijkstra's algorithm over states that are only discovered as the
+/// search goes, for when there are too many to build a `Graph` out of.
+/// `moves` pushes each state reachable from the given one along with the
+/// cost of getting there. Returns the cost of the cheapest way to reach
+/// a state for which `is_goal` holds, and that state.
+pub fn dijkstra_by<S: Clone + Eq + Hash>(start: S,
+        mut moves: impl FnMut(&S, &mut Vec<(S, u64)>),
+        mut is_goal: impl FnMut(&S) -> bool) -> Option<(u64, S)> {
+    // States are numbered as they're found, so the queue only holds numbers
+    let mut ids = FxHashMap::default();
+    let mut states = vec![start.clone()];
+    let mut dists = vec![0];
+    let mut visited = vec![false];
+    ids.insert(start, 0);
+    let mut queue = BinaryHeap::<Reverse<(u64, usize)>>::new();
+    queue.push(Reverse((0, 0)));
+    let mut next = Vec::new();
+    while let Some(Reverse((to_here, node))) = queue.pop() {
+        if visited[node] {
+            // Duplicate entry from distance update
+            continue;
+        }
+        visited[node] = true;
+        if is_goal(&states[node]) {
+            return Some((to_here, states.swap_remove(node)));
+        }
+        moves(&states[node], &mut next);
+        for (state, cost) in next.drain(..) {
+            let proposed = to_here + cost;
+            let id = *ids.entry(state).or_insert_with_key(|state| {
+                states.push(state.clone());
+                dists.push(u64::MAX);
+                visited.push(false);
+                states.len() - 1
+            });
+            if !visited[id] && proposed < dists[id] {
+                dists[id] = proposed;
+                queue.push(Reverse((proposed, id)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -> b -> d costs 5, a -> c -> d costs 3, e is unreachable
+    fn diamond() -> Graph {
+        let mut g = Graph::new();
+        let (a, b, c, d) = (g.node("a"), g.node("b"), g.node("c"), g.node("d"));
+        g.node("e");
+        g.add_edge(a, b, 1);
+        g.add_edge(b, d, 4);
+        g.add_edge(a, c, 2);
+        g.add_edge(c, d, 1);
+        g
+    }
+
+    #[test]
+    fn interns_names() {
+        let mut g = diamond();
+        assert_eq!(g.node("c"), 2);
+        assert_eq!(g.id("e"), Some(4));
+        assert_eq!(g.name(1), Some("b"));
+        assert_eq!(g.len(), 5);
+    }
+
+    #[test]
+    fn searches() {
+        let g = diamond();
+        assert_eq!(g.dijkstra(0), vec![Some(0), Some(1), Some(2), Some(3), None]);
+        assert_eq!(g.shortest_path(0, 3), Some(3));
+        assert_eq!(g.bfs(0), vec![Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(g.dfs(0), vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn searches_implicit_states() {
+        // Reaching 10 from 1 by adding one (cost 1) or doubling (cost 2)
+        let moves = |n: &u32, out: &mut Vec<(u32, u64)>| {
+            out.push((n + 1, 1));
+            out.push((n * 2, 2));
+        };
+        assert_eq!(dijkstra_by(1, moves, |n| *n == 10), Some((6, 10)));
+        assert_eq!(dijkstra_by(1, |_, _| (), |n| *n == 10), None);
+    }
+
+    #[test]
+    fn enumerates_paths() {
+        let g = diamond();
+        let mut paths = vec![];
+        g.for_each_path(0, 3, (), &mut |_, _| Some(()), &mut |p| paths.push(p.to_vec()));
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+        // Only paths that avoid b
+        assert_eq!(g.count_paths(0, 3, (), |_, n| if n == 1 { None } else { Some(()) }), 1);
+    }
+}
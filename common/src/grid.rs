@@ -0,0 +1,225 @@
+use crate::{Error, Result};
+use ndarray::Array2;
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position in a grid
+pub type Coord = (usize, usize);
+
+/// Steps to the four orthogonal neighbors: up, down, left, right
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Steps to all eight neighbors, row by row
+pub const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1),
+];
+
+/// A rectangular grid of cells, indexed by `(row, column)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a `rows` by `cols` grid by calling `f` on each position
+    pub fn from_fn(rows: usize, cols: usize, f: impl FnMut(Coord) -> T) -> Self {
+        Self { cells: Array2::from_shape_fn((rows, cols), f) }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn get(&self, (i, j): Coord) -> Option<&T> {
+        self.cells.get((i, j))
+    }
+
+    pub fn get_mut(&mut self, (i, j): Coord) -> Option<&mut T> {
+        self.cells.get_mut((i, j))
+    }
+
+    /// The position `(di, dj)` away from `coord`, if it's inside the grid
+    pub fn offset(&self, (i, j): Coord, (di, dj): (isize, isize)) -> Option<Coord> {
+        let i = i.checked_add_signed(di)?;
+        let j = j.checked_add_signed(dj)?;
+        if i < self.rows() && j < self.cols() { Some((i, j)) } else { None }
+    }
+
+    /// The position `(di, dj)` away from `coord` when the grid is a torus,
+    /// wrapping around each edge to the opposite one
+    pub fn wrapping_offset(&self, (i, j): Coord, (di, dj): (isize, isize)) -> Coord {
+        let i = (i as isize + di).rem_euclid(self.rows() as isize) as usize;
+        let j = (j as isize + dj).rem_euclid(self.cols() as isize) as usize;
+        (i, j)
+    }
+
+    /// The positions of the up to four orthogonal neighbors of `coord`
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.offset(coord, d))
+    }
+
+    /// The positions of the up to eight neighbors of `coord`, diagonals included
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ALL_AROUND.iter().filter_map(move |&d| self.offset(coord, d))
+    }
+
+    /// Every position, in row-major order
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let cols = self.cols();
+        (0..self.rows()).flat_map(move |i| (0..cols).map(move |j| (i, j)))
+    }
+
+    /// Every cell with its position, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.indexed_iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.cells.indexed_iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.map(f) }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses lines of cells, which must all be the same length, turning
+    /// each byte into a cell with `cell`. Errors are attributed to `day`
+    /// and describe bad bytes as not being a `what`.
+    pub fn parse_cells(day: u8, input: &str, what: &str,
+            cell: impl Fn(u8) -> Option<T>) -> Result<Self> {
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            for (j, b) in line.bytes().enumerate() {
+                let c = cell(b).ok_or_else(|| Error::parse(day, i + 1, j + 1,
+                    format!("{:?} is not a {}", b as char, what)))?;
+                cells.push(c);
+            }
+            if rows == 0 {
+                cols = line.len();
+            } else if line.len() != cols {
+                return Err(Error::parse(day, i + 1, 1,
+                    format!("Row has {} cells, expected {}", line.len(), cols)));
+            }
+            rows += 1;
+        }
+        if cols == 0 {
+            return Err(Error::parse(day, 1, 1, "Empty grid"));
+        }
+        let cells = Array2::from_shape_vec((rows, cols), cells)
+            .expect("Row lengths were checked");
+        Ok(Self { cells })
+    }
+}
+
+impl Grid<u8> {
+    /// Parses lines of decimal digits, which must all be the same length
+    pub fn parse_digits(day: u8, input: &str) -> Result<Self> {
+        Self::parse_cells(day, input, "digit",
+            |b| if b >= b'0' && b <= b'9' { Some(b - b'0') } else { None })
+    }
+}
+
+impl Grid<bool> {
+    /// Parses lines of `#` (set) and `.` (unset)
+    pub fn parse_pixels(day: u8, input: &str) -> Result<Self> {
+        Self::parse_cells(day, input, "pixel", |b| match b {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        })
+    }
+
+    /// Draws set cells as `#` on a background of `.`, one row per line
+    pub fn render(&self) -> String {
+        self.map(|&p| if p { '#' } else { '.' }).to_string()
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+    fn index(&self, (i, j): Coord) -> &T {
+        &self.cells[(i, j)]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, (i, j): Coord) -> &mut T {
+        &mut self.cells[(i, j)]
+    }
+}
+
+/// One line per row, with the cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.rows().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGITS: &'static str = "123\n456\n";
+
+    #[test]
+    fn parses_and_displays_digits() {
+        let grid = Grid::parse_digits(0, DIGITS).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = Grid::parse_digits(0, "12\n3\n").unwrap_err();
+        assert_eq!(err.to_string(), "day 0, line 2, column 1: Row has 1 cells, expected 2");
+        let err = Grid::parse_digits(0, "12\n3x\n").unwrap_err();
+        assert_eq!(err.to_string(), "day 0, line 2, column 2: 'x' is not a digit");
+    }
+
+    #[test]
+    fn renders_pixels() {
+        let grid = Grid::parse_pixels(0, "#.\n.#\n").unwrap();
+        assert!(grid[(1, 1)] && !grid[(0, 1)]);
+        assert_eq!(grid.render(), "#.\n.#");
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::parse_digits(0, DIGITS).unwrap();
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+        assert_eq!(grid.neighbors4((1, 2)).count(), 2);
+    }
+
+    #[test]
+    fn wraps_around_edges() {
+        let grid = Grid::parse_digits(0, DIGITS).unwrap();
+        assert_eq!(grid.wrapping_offset((0, 2), (0, 1)), (0, 0));
+        assert_eq!(grid.wrapping_offset((0, 0), (-1, -1)), (1, 2));
+        assert_eq!(grid.wrapping_offset((1, 1), (5, 0)), (0, 1));
+    }
+}
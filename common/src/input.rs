@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Where a solution should get its input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The puzzle input embedded in the binary
+    Puzzle,
+    /// The sample input from the problem statement
+    Sample,
+    /// Standard input, read to the end
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Picks a source from the command line: `sample` selects the sample,
+    /// `-` reads standard input, any other argument is a file path,
+    /// and no argument falls back to the embedded puzzle.
+    pub fn from_args() -> Self {
+        match std::env::args().nth(1) {
+            None => Source::Puzzle,
+            Some(a) if a == "sample" => Source::Sample,
+            Some(a) => Source::from_path(a),
+        }
+    }
+
+    /// A file to read from, where `-` means standard input
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if path.as_os_str() == "-" {
+            Source::Stdin
+        } else {
+            Source::File(path)
+        }
+    }
+
+    /// Reads the input. Days whose puzzle input isn't checked in embed
+    /// an empty one, which is an error to read.
+    pub fn read(&self, puzzle: &'static str, sample: &'static str)
+            -> io::Result<Cow<'static, str>> {
+        match self {
+            Source::Puzzle if puzzle.is_empty() => Err(io::Error::new(io::ErrorKind::NotFound,
+                "no puzzle input is embedded for this day")),
+            Source::Puzzle => Ok(Cow::Borrowed(puzzle)),
+            Source::Sample => Ok(Cow::Borrowed(sample)),
+            Source::Stdin => {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                Ok(Cow::Owned(buffer))
+            },
+            Source::File(path) => fs::read_to_string(path).map(Cow::Owned),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Puzzle => write!(f, "puzzle"),
+            Source::Sample => write!(f, "sample"),
+            Source::Stdin => write!(f, "-"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Reads the input selected by the command line arguments,
+/// using the embedded `puzzle` and `sample` as fallbacks.
+pub fn load(puzzle: &'static str, sample: &'static str)
+        -> io::Result<Cow<'static, str>> {
+    Source::from_args().read(puzzle, sample)
+}
@@ -0,0 +1,59 @@
+pub mod error;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod logger;
+pub mod solution;
+pub mod timing;
+
+pub use error::{Error, Location, Result};
+pub use grid::Grid;
+pub use solution::{solve, Answer, Answers, PartAnswer, Solution};
+pub use timing::Timings;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the two halves of each day's puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A, B,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::A, Part::B];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "a" | "A" => Ok(Part::A),
+            "b" | "B" => Ok(Part::B),
+            _ => Err(format!("Unknown part {:?}, expected a or b", s)),
+        }
+    }
+}
+
+/// Prints an answer as "Part a: ...", putting answers that span
+/// several lines (like rendered grids) below the label, followed by
+/// any auxiliary output.
+pub fn print_answer(answer: &PartAnswer) {
+    if answer.answer.contains('\n') {
+        println!("Part {}:\n{}", answer.part, answer.answer);
+    } else {
+        println!("Part {}: {}", answer.part, answer.answer);
+    }
+    if let Some(aux) = &answer.aux {
+        println!("{}", aux);
+    }
+}
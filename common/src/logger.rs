@@ -0,0 +1,48 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes log records to stderr as "LEVEL target: message",
+/// so tracing never mixes with answers on stdout.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{:<5} {}: {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// The level for a count of `-v` flags: warnings by default, then info,
+/// debug and trace.
+pub fn level_for(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Installs the stderr logger. Later calls only change the level.
+pub fn init(level: LevelFilter) {
+    // Fails only if a logger is already installed, which is fine
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+/// Installs the stderr logger at the level named by `AOC_LOG`
+/// (`error`, `warn`, `info`, `debug` or `trace`), defaulting to warnings.
+pub fn init_from_env() {
+    let level = std::env::var("AOC_LOG").ok()
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(LevelFilter::Warn);
+    init(level);
+}
@@ -0,0 +1,116 @@
+use crate::{input, logger, print_answer, Error, Part, Result};
+
+use std::fmt::Display;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// A day's puzzle, split into parsing and the two parts, so that tooling
+/// can drive every day the same way.
+pub trait Solution {
+    /// The day of the advent calendar this solves
+    const DAY: u8;
+
+    type Input;
+    type A: Answer;
+    type B: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_a(input: &Self::Input) -> Result<Self::A>;
+    fn part_b(input: &Self::Input) -> Result<Self::B>;
+}
+
+/// The result of one part: what gets submitted, plus anything worth
+/// showing alongside it (a final tree, a rendered grid, ...).
+pub trait Answer: Display {
+    fn aux(&self) -> Option<String> {
+        None
+    }
+}
+
+macro_rules! plain_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+plain_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+/// The `sample_part_a` and `sample_part_b` tests every day has, which
+/// solve its `SAMPLE` and compare the answers as they'd be printed
+#[macro_export]
+macro_rules! sample_tests {
+    ($solution:ty, $a:expr, $b:expr) => {
+        #[test]
+        fn sample_part_a() {
+            use $crate::Solution;
+            let input = <$solution>::parse(SAMPLE).unwrap();
+            assert_eq!(<$solution>::part_a(&input).unwrap().to_string(), $a.to_string());
+        }
+
+        #[test]
+        fn sample_part_b() {
+            use $crate::Solution;
+            let input = <$solution>::parse(SAMPLE).unwrap();
+            assert_eq!(<$solution>::part_b(&input).unwrap().to_string(), $b.to_string());
+        }
+    };
+}
+
+/// One rendered answer and how long it took to compute
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub part: Part,
+    pub answer: String,
+    pub aux: Option<String>,
+    pub time: Duration,
+}
+
+impl PartAnswer {
+    fn new<T: Answer>(part: Part, answer: T, time: Duration) -> Self {
+        Self { part, answer: answer.to_string(), aux: answer.aux(), time }
+    }
+}
+
+/// The answers to some of a day's parts, with the time spent parsing
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub parse_time: Duration,
+    pub parts: Vec<PartAnswer>,
+}
+
+/// Parses `input` once and solves each of `parts` on it, stopping at the
+/// first failure.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Answers> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let parts = parts.iter().copied().map(|part| {
+        let start = Instant::now();
+        Ok(match part {
+            Part::A => PartAnswer::new(part, S::part_a(&parsed)?, start.elapsed()),
+            Part::B => PartAnswer::new(part, S::part_b(&parsed)?, start.elapsed()),
+        })
+    }).collect::<Result<_>>()?;
+    Ok(Answers { parse_time, parts })
+}
+
+/// The whole of a day's binary: load the input picked on the command line
+/// and print the answers to both parts.
+pub fn main<S: Solution>(puzzle: &'static str, sample: &'static str)
+        -> ExitCode {
+    logger::init_from_env();
+    let answers = input::load(puzzle, sample).map_err(Error::from)
+        .and_then(|input_str| solve::<S>(&input_str, &Part::BOTH));
+    match answers {
+        Ok(answers) => {
+            for answer in &answers.parts {
+                print_answer(answer);
+            }
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
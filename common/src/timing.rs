@@ -0,0 +1,41 @@
+use crate::{Result, Solution};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How long each stage of a day took, as the median over the repeats
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_a: Duration,
+    pub part_b: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_a + self.part_b
+    }
+}
+
+fn median<T>(repeats: usize, mut stage: impl FnMut() -> Result<T>) -> Result<Duration> {
+    let mut samples = Vec::with_capacity(repeats);
+    for _ in 0..repeats {
+        let start = Instant::now();
+        black_box(stage()?);
+        samples.push(start.elapsed());
+    }
+    samples.sort_unstable();
+    Ok(samples[samples.len() / 2])
+}
+
+/// Times parsing `input` and solving both parts on it, each stage run
+/// `repeats` times (at least once) so one slow run doesn't skew the result.
+pub fn time<S: Solution>(input: &str, repeats: usize) -> Result<Timings> {
+    let repeats = repeats.max(1);
+    let parsed = S::parse(input)?;
+    Ok(Timings {
+        parse: median(repeats, || S::parse(black_box(input)))?,
+        part_a: median(repeats, || S::part_a(black_box(&parsed)))?,
+        part_b: median(repeats, || S::part_b(black_box(&parsed)))?,
+    })
+}
@@ -0,0 +1,199 @@
+//! Day 1: Sonar Sweep. Counting how often the sea floor gets deeper.
+
+use aoc_common::{Error, Result, Solution};
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+pub const PUZZLE: &'static str = include_str!("input01");
+pub const SAMPLE: &'static str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+// `i` is the 0-based line number
+fn parse_depth(i: usize, l: &str) -> Result<u32> {
+  l.trim().parse::<u32>().map_err(|e| Error::parse(Day01::DAY, i + 1, 1,
+    format!("{:?} is not a depth: {}", l, e)))
+}
+
+/// One depth per line
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+  input.lines().enumerate().map(|(i, l)| parse_depth(i, l)).collect()
+}
+
+/// Depths read one line at a time, for input that doesn't fit in memory
+/// or hasn't all arrived yet
+pub fn read_depths(reader: impl BufRead) -> impl Iterator<Item = Result<u32>> {
+  reader.lines().enumerate()
+    .map(|(i, l)| l.map_err(Error::from).and_then(|l| parse_depth(i, &l)))
+}
+
+/// How many depths are deeper than the one before
+pub fn part_a(input: &[u32]) -> u32 {
+  input.windows(2).filter(|w| w[1] > w[0]).count() as u32
+}
+
+/// How many sums of three consecutive depths are deeper than the one before
+pub fn part_b(input: &[u32]) -> u32 {
+  window_increases(input, 3, 1)
+}
+
+/// How many sums of `k` consecutive depths are larger than the sum of the
+/// `k` depths starting `lag` earlier. Both sums are kept up to date as the
+/// windows slide along, since each step only adds one depth to a window
+/// and drops another.
+pub fn window_increases(input: &[u32], k: usize, lag: usize) -> u32 {
+  if k + lag > input.len() {
+    return 0;
+  }
+  let sum = |w: &[u32]| w.iter().map(|&d| d as u64).sum::<u64>();
+  let mut earlier = sum(&input[..k]);
+  let mut later = sum(&input[lag..lag + k]);
+  let mut ret = (later > earlier) as u32;
+  for start in 1..=input.len() - k - lag {
+    earlier = earlier + input[start + k - 1] as u64 - input[start - 1] as u64;
+    later = later + input[start + lag + k - 1] as u64 - input[start + lag - 1] as u64;
+    if later > earlier {
+      ret += 1;
+    }
+  }
+  ret
+}
+
+/// `window_increases` one depth at a time, keeping only the last `k + lag`
+#[derive(Clone, Debug)]
+pub struct Detector {
+  k: usize,
+  lag: usize,
+  recent: VecDeque<u32>,
+  earlier: u64,
+  later: u64,
+  increases: u32,
+}
+
+impl Detector {
+  pub fn new(k: usize, lag: usize) -> Self {
+    Detector { k, lag, recent: VecDeque::with_capacity(k + lag + 1),
+      earlier: 0, later: 0, increases: 0 }
+  }
+
+  /// Takes the next depth and returns how many increases there have been
+  pub fn push(&mut self, depth: u32) -> u32 {
+    let (k, lag) = (self.k, self.lag);
+    self.recent.push_back(depth);
+    let n = self.recent.len();
+    // `later` is the sum of the last k depths and `earlier` of the k before
+    // the last `lag`, so each gains one depth and loses one as they slide
+    self.later += depth as u64;
+    if n > k {
+      self.later -= self.recent[n - 1 - k] as u64;
+    }
+    if n > lag {
+      self.earlier += self.recent[n - 1 - lag] as u64;
+    }
+    if n > k + lag {
+      self.earlier -= self.recent.pop_front().expect("Has k + lag + 1 depths") as u64;
+    }
+    if n >= k + lag && self.later > self.earlier {
+      self.increases += 1;
+    }
+    self.increases
+  }
+
+  pub fn increases(&self) -> u32 {
+    self.increases
+  }
+}
+
+/// The part a and part b counts so far
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Increases {
+  pub single: u32,
+  pub window: u32,
+}
+
+/// The running counts for both parts after each depth
+pub fn sweep(depths: impl IntoIterator<Item = u32>) -> impl Iterator<Item = Increases> {
+  let mut single = Detector::new(1, 1);
+  let mut window = Detector::new(3, 1);
+  depths.into_iter().map(move |d| Increases { single: single.push(d), window: window.push(d) })
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  const DAY: u8 = 1;
+  type Input = Vec<u32>;
+  type A = u32;
+  type B = u32;
+
+  fn parse(input: &str) -> Result<Vec<u32>> {
+    parse(input)
+  }
+
+  fn part_a(input: &Vec<u32>) -> Result<u32> {
+    Ok(part_a(input))
+  }
+
+  fn part_b(input: &Vec<u32>) -> Result<u32> {
+    Ok(part_b(input))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  aoc_common::sample_tests!(Day01, 7, 5);
+
+  #[test]
+  fn windows_and_lags() {
+    let input = Day01::parse(SAMPLE).unwrap();
+    assert_eq!(window_increases(&input, 1, 1), 7);
+    let naive = |k: usize, lag: usize| {
+      let sums: Vec<u32> = input.windows(k).map(|w| w.iter().sum()).collect();
+      sums.iter().zip(sums.iter().skip(lag)).filter(|(a, b)| b > a).count() as u32
+    };
+    for k in 1..=5 {
+      for lag in 1..=5 {
+        assert_eq!(window_increases(&input, k, lag), naive(k, lag), "k = {}, lag = {}", k, lag);
+      }
+    }
+    assert_eq!(window_increases(&input, 8, 3), 0);
+  }
+
+  #[test]
+  fn detects_while_streaming() {
+    let input = Day01::parse(SAMPLE).unwrap();
+    let counts: Vec<Increases> = sweep(input.iter().copied()).collect();
+    assert_eq!(counts[3], Increases { single: 3, window: 1 });
+    assert_eq!(counts.last(), Some(&Increases { single: 7, window: 5 }));
+    for (k, lag) in [(1, 3), (4, 2), (2, 6), (11, 1)] {
+      let mut detector = Detector::new(k, lag);
+      input.iter().for_each(|&d| { detector.push(d); });
+      assert_eq!(detector.increases(), window_increases(&input, k, lag), "k = {}, lag = {}", k, lag);
+    }
+  }
+
+  #[test]
+  fn streams_from_readers() {
+    let depths: Result<Vec<u32>> = read_depths(SAMPLE.as_bytes()).collect();
+    assert_eq!(sweep(depths.unwrap()).last().unwrap().window, 5);
+    let err = read_depths("199\n200\ndeep\n210\n".as_bytes()).find_map(|r| r.err()).unwrap();
+    assert_eq!(err.to_string(),
+      "day 1, line 3, column 1: \"deep\" is not a depth: invalid digit found in string");
+  }
+
+  #[test]
+  fn rejects_non_numbers() {
+    let err = Day01::parse("199\n2o0\n").unwrap_err();
+    assert!(err.to_string().starts_with("day 1, line 2, column 1:"), "{}", err);
+  }
+}
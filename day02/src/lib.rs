@@ -0,0 +1,499 @@
+//! Day 2: Dive! Steering the submarine with a list of commands.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+use std::fmt;
+use std::io::{self, Write};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Horizontal position and depth
+pub type Point = (i32, i32);
+
+/// Position, depth and aim, for the part b reading of the commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Aimed {
+  pub x: i32,
+  pub y: i32,
+  pub aim: i32,
+}
+
+/// One line of the course, like `forward 5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmd {
+  Fwd(i32),
+  Down(i32),
+  Up(i32),
+  /// The opposite of forward
+  Back(i32),
+}
+
+/// Everything after this on a line is a comment
+pub const COMMENT: char = '#';
+
+/// One line of a navigation script, which is a command, a `hold N` that
+/// repeats the command before it N more times, or nothing but a comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statement {
+  Cmd(Cmd),
+  Hold(u32),
+  Blank,
+}
+
+/// A line of the script that moves the submarine: a command, done
+/// `count` times in a row. Holds are kept as counts rather than copies,
+/// so that a long hold doesn't take up memory, and `parse` checks that
+/// the command's parameter times the count fits in an i32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Leg {
+  /// 1-based line in the script
+  pub line: usize,
+  pub cmd: Cmd,
+  pub count: u32,
+}
+
+impl Cmd {
+  /// The same command with its parameter multiplied by `n`, or None if
+  /// that doesn't fit in an i32
+  pub fn times(self, n: u32) -> Option<Cmd> {
+    let n = i32::try_from(n).ok()?;
+    Some(match self {
+      Cmd::Fwd(d) => Cmd::Fwd(d.checked_mul(n)?),
+      Cmd::Down(d) => Cmd::Down(d.checked_mul(n)?),
+      Cmd::Up(d) => Cmd::Up(d.checked_mul(n)?),
+      Cmd::Back(d) => Cmd::Back(d.checked_mul(n)?),
+    })
+  }
+}
+
+impl fmt::Display for Cmd {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Cmd::Fwd(d) => write!(f, "forward {}", d),
+      Cmd::Down(d) => write!(f, "down {}", d),
+      Cmd::Up(d) => write!(f, "up {}", d),
+      Cmd::Back(d) => write!(f, "back {}", d),
+    }
+  }
+}
+
+impl FromStr for Cmd {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    let err = |col, msg: String| Error::parse(Day02::DAY, 1, col, msg);
+    let mut iter = s.split_whitespace();
+    let command = iter.next().ok_or_else(|| err(1, "No command".into()))?;
+    let cmd: fn(i32) -> Cmd = match command.to_ascii_lowercase().as_str() {
+      "forward" => Cmd::Fwd,
+      "down" => Cmd::Down,
+      "up" => Cmd::Up,
+      "back" => Cmd::Back,
+      _ => return Err(err(column(s, command), format!("Unknown command {:?}", command))),
+    };
+    let param = iter.next()
+      .ok_or_else(|| err(s.len() + 1, format!("No parameter after {:?}", command)))?;
+    let value = param.parse::<i32>()
+      .map_err(|e| err(column(s, param), format!("Bad parameter {:?}: {}", param, e)))?;
+    if let Some(extra) = iter.next() {
+      return Err(err(column(s, extra), format!("Unexpected {:?} after the parameter", extra)));
+    }
+    Ok(cmd(value))
+  }
+}
+
+impl FromStr for Statement {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    // Slicing keeps the columns of what's left the same
+    let code = s.split(COMMENT).next().unwrap_or("");
+    let mut iter = code.split_whitespace();
+    match iter.next() {
+      None => Ok(Statement::Blank),
+      Some(word) if word.eq_ignore_ascii_case("hold") => {
+        let count = iter.next().ok_or_else(|| Error::parse(Day02::DAY, 1, code.len() + 1,
+          format!("No count after {:?}", word)))?;
+        let n = count.parse::<u32>().map_err(|e| Error::parse(Day02::DAY, 1, column(s, count),
+          format!("Bad count {:?}: {}", count, e)))?;
+        if n > i32::MAX as u32 {
+          return Err(Error::parse(Day02::DAY, 1, column(s, count),
+            format!("Count {} is more than {}", n, i32::MAX)));
+        }
+        if let Some(extra) = iter.next() {
+          return Err(Error::parse(Day02::DAY, 1, column(s, extra),
+            format!("Unexpected {:?} after the count", extra)));
+        }
+        Ok(Statement::Hold(n))
+      },
+      Some(_) => code.parse().map(Statement::Cmd),
+    }
+  }
+}
+
+/// The legs of a navigation script. A `hold` is a leg of its own,
+/// repeating the command on the leg before it.
+pub fn parse(input: &str) -> Result<Vec<Leg>> {
+  let mut legs: Vec<Leg> = vec![];
+  for (i, l) in input.lines().enumerate() {
+    match l.parse().map_err(|e: Error| e.offset_lines(i))? {
+      Statement::Cmd(cmd) => legs.push(Leg { line: i + 1, cmd, count: 1 }),
+      Statement::Hold(count) => {
+        let mut words = l.split_whitespace();
+        let word = words.next().unwrap_or(l);
+        let prev = legs.last().ok_or_else(|| Error::parse(Day02::DAY, i + 1, column(l, word),
+          format!("{:?} has no command before it to repeat", word)))?;
+        if prev.cmd.times(count).is_none() {
+          let count_str = words.next().unwrap_or(l);
+          return Err(Error::parse(Day02::DAY, i + 1, column(l, count_str),
+            format!("{:?} done {} times goes past {}", prev.cmd.to_string(), count, i32::MAX)));
+        }
+        legs.push(Leg { line: i + 1, cmd: prev.cmd, count });
+      },
+      Statement::Blank => (),
+    }
+  }
+  Ok(legs)
+}
+
+/// A reading of the commands: the state the submarine keeps track of,
+/// and how each command changes it
+pub trait SubmarineModel {
+  type State: Copy;
+
+  /// The state at the surface, before any commands
+  fn start(&self) -> Self::State;
+  fn step(&self, state: Self::State, cmd: Cmd) -> Self::State;
+  /// `cmd` done `n` times. This does them one at a time, which for the
+  /// longest hold is 2^31 steps, so models that can do it faster should.
+  fn step_n(&self, state: Self::State, cmd: Cmd, n: u32) -> Self::State {
+    (0..n).fold(state, |state, _| self.step(state, cmd))
+  }
+  /// Horizontal position and depth
+  fn position(&self, state: &Self::State) -> Point;
+}
+
+/// Part a: down and up change the depth directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Direct;
+
+impl SubmarineModel for Direct {
+  type State = Point;
+
+  fn start(&self) -> Point {
+    (0, 0)
+  }
+
+  fn step(&self, point: Point, cmd: Cmd) -> Point {
+    let (x, y) = point;
+    match cmd {
+      Cmd::Fwd(d) => (x + d, y),
+      Cmd::Down(d) => (x, y + d),
+      Cmd::Up(d) => (x, y - d),
+      Cmd::Back(d) => (x - d, y),
+    }
+  }
+
+  fn step_n(&self, point: Point, cmd: Cmd, n: u32) -> Point {
+    self.step(point, cmd.times(n).expect("Held commands are checked to fit when parsed"))
+  }
+
+  fn position(&self, point: &Point) -> Point {
+    *point
+  }
+}
+
+/// Part b: down and up change the aim, and forward dives along it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WithAim;
+
+impl SubmarineModel for WithAim {
+  type State = Aimed;
+
+  fn start(&self) -> Aimed {
+    Aimed::default()
+  }
+
+  fn step(&self, p: Aimed, cmd: Cmd) -> Aimed {
+    let Aimed {x, y, aim} = p;
+    match cmd {
+      Cmd::Fwd(d) => Aimed {x: x + d, y: y + d * aim, aim},
+      Cmd::Down(d) => Aimed {x, y, aim: aim + d},
+      Cmd::Up(d) => Aimed {x, y, aim: aim - d},
+      Cmd::Back(d) => Aimed {x: x - d, y: y - d * aim, aim},
+    }
+  }
+
+  // None of the commands change the aim and move at once, so n of them
+  // are one command n times as large
+  fn step_n(&self, p: Aimed, cmd: Cmd, n: u32) -> Aimed {
+    self.step(p, cmd.times(n).expect("Held commands are checked to fit when parsed"))
+  }
+
+  fn position(&self, p: &Aimed) -> Point {
+    (p.x, p.y)
+  }
+}
+
+/// Where `model`'s reading of `cmds` ends up, starting from the surface
+pub fn run_commands<M: SubmarineModel>(model: &M, cmds: &[Cmd]) -> M::State {
+  cmds.iter().fold(model.start(), |state, &cmd| model.step(state, cmd))
+}
+
+/// Where `model`'s reading of a script's `legs` ends up, doing each held
+/// command with one `step_n`
+pub fn run_legs<M: SubmarineModel>(model: &M, legs: &[Leg]) -> M::State {
+  legs.iter().fold(model.start(), |state, leg| model.step_n(state, leg.cmd, leg.count))
+}
+
+/// Horizontal position times depth at the end of the course
+pub fn final_product<M: SubmarineModel>(model: &M, cmds: &[Cmd]) -> i32 {
+  let (x_f, y_f) = model.position(&run_commands(model, cmds));
+  x_f * y_f
+}
+
+/// States that can be written as the fields of a CSV row
+pub trait CsvFields {
+  /// Names for the fields, separated by commas
+  const HEADER: &'static str;
+  fn fields(&self) -> String;
+}
+
+impl CsvFields for Point {
+  const HEADER: &'static str = "x,y";
+  fn fields(&self) -> String {
+    format!("{},{}", self.0, self.1)
+  }
+}
+
+impl CsvFields for Aimed {
+  const HEADER: &'static str = "x,y,aim";
+  fn fields(&self) -> String {
+    format!("{},{},{}", self.x, self.y, self.aim)
+  }
+}
+
+/// The state after a step of a leg, with where that leaves the submarine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Waypoint<S> {
+  pub leg: Leg,
+  /// How many times the leg's command has been done, from 1 to its count
+  pub step: u32,
+  pub state: S,
+  pub position: Point,
+}
+
+/// The states at the end of each leg of a course, along with the deepest
+/// point and first surfacing anywhere on it, including partway through a hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory<S> {
+  pub waypoints: Vec<Waypoint<S>>,
+  max_depth: i32,
+  surfaced: Option<Waypoint<S>>,
+}
+
+impl<S> Trajectory<S> {
+  /// The deepest the submarine gets, which is never above the surface it starts at
+  pub fn max_depth(&self) -> i32 {
+    self.max_depth
+  }
+
+  /// The first step that brings the submarine back up to the surface,
+  /// or takes it above it
+  pub fn surfaced(&self) -> Option<&Waypoint<S>> {
+    self.surfaced.as_ref()
+  }
+}
+
+impl<S: CsvFields> Trajectory<S> {
+  /// One row per waypoint, under a header row
+  pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "line,command,count,{}", S::HEADER)?;
+    for w in self.waypoints.iter() {
+      writeln!(out, "{},{},{},{}", w.leg.line, w.leg.cmd, w.leg.count, w.state.fields())?;
+    }
+    Ok(())
+  }
+}
+
+/// The state after each of `legs` under `model`. A held command is one
+/// waypoint, at the end of the hold, so a long hold doesn't make a long
+/// trajectory, but it's still stepped through one command at a time so
+/// that the depth is checked all the way along it.
+pub fn trajectory<M: SubmarineModel>(model: &M, legs: &[Leg]) -> Trajectory<M::State> {
+  let mut state = model.start();
+  let mut max_depth = 0;
+  let mut surfaced = None;
+  let mut waypoints = Vec::with_capacity(legs.len());
+  for &leg in legs {
+    for step in 1..=leg.count {
+      let prev_depth = model.position(&state).1;
+      state = model.step(state, leg.cmd);
+      let position = model.position(&state);
+      max_depth = max_depth.max(position.1);
+      if surfaced.is_none() && (position.1 < 0 || (position.1 == 0 && prev_depth > 0)) {
+        surfaced = Some(Waypoint { leg, step, state, position });
+      }
+    }
+    waypoints.push(Waypoint { leg, step: leg.count, state, position: model.position(&state) });
+  }
+  Trajectory { waypoints, max_depth, surfaced }
+}
+
+pub fn part_a(legs: &[Leg]) -> i32 {
+  let (x_f, y_f) = run_legs(&Direct, legs);
+  x_f * y_f
+}
+
+pub fn part_b(legs: &[Leg]) -> i32 {
+  let Aimed {x, y, ..} = run_legs(&WithAim, legs);
+  x * y
+}
+
+pub const PUZZLE: &'static str = include_str!("input02");
+pub const SAMPLE: &'static str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  const DAY: u8 = 2;
+  type Input = Vec<Leg>;
+  type A = i32;
+  type B = i32;
+
+  fn parse(input: &str) -> Result<Vec<Leg>> {
+    parse(input)
+  }
+
+  fn part_a(legs: &Vec<Leg>) -> Result<i32> {
+    Ok(part_a(legs))
+  }
+
+  fn part_b(legs: &Vec<Leg>) -> Result<i32> {
+    Ok(part_b(legs))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  aoc_common::sample_tests!(Day02, 150, 900);
+
+  /// Part a, but the submarine can't go above the surface or below `floor`
+  struct Floored {
+    floor: i32,
+  }
+
+  impl SubmarineModel for Floored {
+    type State = Point;
+
+    fn start(&self) -> Point {
+      Direct.start()
+    }
+
+    fn step(&self, point: Point, cmd: Cmd) -> Point {
+      let (x, y) = Direct.step(point, cmd);
+      (x, y.clamp(0, self.floor))
+    }
+
+    fn position(&self, point: &Point) -> Point {
+      *point
+    }
+  }
+
+  #[test]
+  fn runs_other_models() {
+    let cmds: Vec<Cmd> = SAMPLE.lines().map(|l| l.parse().unwrap()).collect();
+    assert_eq!(run_commands(&WithAim, &cmds), Aimed {x: 15, y: 60, aim: 10});
+    // Down 5, up 3 from the floor of 4, then down 8 to the floor again
+    assert_eq!(run_commands(&Floored { floor: 4 }, &cmds), (15, 4));
+    assert_eq!(final_product(&Floored { floor: 4 }, &cmds), 60);
+    assert_eq!(run_legs(&Floored { floor: 4 }, &Day02::parse(SAMPLE).unwrap()), (15, 4));
+  }
+
+  #[test]
+  fn records_trajectories() {
+    let input = Day02::parse(SAMPLE).unwrap();
+    let path = trajectory(&WithAim, &input);
+    assert_eq!(path.waypoints.len(), 6);
+    assert_eq!(path.waypoints[2].state, Aimed {x: 13, y: 40, aim: 5});
+    assert_eq!(path.waypoints.last().unwrap().state, run_legs(&WithAim, &input));
+    assert_eq!(path.max_depth(), 60);
+    assert_eq!(path.surfaced(), None);
+
+    let bobbing = Day02::parse("down 2\nforward 1\nup 2\ndown 1\nup 3").unwrap();
+    let path = trajectory(&Direct, &bobbing);
+    assert_eq!(path.max_depth(), 2);
+    assert_eq!(path.surfaced().map(|w| w.leg.line), Some(3));
+    let mut csv = vec![];
+    path.write_csv(&mut csv).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "line,command,count,x,y
+1,down 2,1,0,2
+2,forward 1,1,1,2
+3,up 2,1,1,0
+4,down 1,1,1,1
+5,up 3,1,1,-2
+");
+
+    // The hold goes through the surface on its second step and ends well above it
+    let path = trajectory(&Direct, &parse("down 3\nup 1\nhold 5").unwrap());
+    assert_eq!(path.waypoints.len(), 3);
+    assert_eq!(path.waypoints[2].position, (0, -3));
+    let surfaced = path.surfaced().unwrap();
+    assert_eq!((surfaced.leg.line, surfaced.step, surfaced.position), (3, 2, (0, 0)));
+    assert_eq!(path.max_depth(), 3);
+  }
+
+  #[test]
+  fn expands_scripts() {
+    let script = "# Leave the dock\nFORWARD 2\nhold 2  # keep going\n\nDown 3\nback 1\n";
+    let legs = parse(script).unwrap();
+    assert_eq!(legs, [
+      Leg { line: 2, cmd: Cmd::Fwd(2), count: 1 },
+      Leg { line: 3, cmd: Cmd::Fwd(2), count: 2 },
+      Leg { line: 5, cmd: Cmd::Down(3), count: 1 },
+      Leg { line: 6, cmd: Cmd::Back(1), count: 1 },
+    ]);
+    assert_eq!(run_legs(&Direct, &legs), (5, 3));
+    assert_eq!(run_legs(&WithAim, &legs), Aimed {x: 5, y: -3, aim: 3});
+    assert_eq!(trajectory(&Direct, &legs).waypoints[1].position, (6, 0));
+  }
+
+  #[test]
+  fn holds_without_copying() {
+    let err = parse("down 1\nforward 1\nhold 2000000000\nup 1\nhold 4000000000").unwrap_err();
+    assert_eq!(err.to_string(), "day 2, line 5, column 6: Count 4000000000 is more than 2147483647");
+    let legs = parse("forward 1\nhold 2000000000\nup 1").unwrap();
+    assert_eq!(run_legs(&Direct, &legs), (2000000001, -1));
+    assert_eq!(run_legs(&Floored { floor: 4 }, &parse("down 3\nhold 3").unwrap()), (0, 4));
+    let err = parse("up 1\nforward 2\nhold 2000000000").unwrap_err();
+    assert_eq!(err.to_string(),
+      "day 2, line 3, column 6: \"forward 2\" done 2000000000 times goes past 2147483647");
+    let legs = parse("forward 2\nhold 1073741822").unwrap();
+    assert_eq!(run_legs(&WithAim, &legs), Aimed {x: 2147483646, y: 0, aim: 0});
+  }
+
+  #[test]
+  fn reports_bad_tokens() {
+    let err = parse("forward 1\n  sideways 3").unwrap_err();
+    assert_eq!(err.to_string(), "day 2, line 2, column 3: Unknown command \"sideways\"");
+    let err = parse("# nothing yet\nhold 2").unwrap_err();
+    assert_eq!(err.to_string(), "day 2, line 2, column 1: \"hold\" has no command before it to repeat");
+    let err = parse("up 1\nhold -2").unwrap_err();
+    assert!(err.to_string().starts_with("day 2, line 2, column 6: Bad count \"-2\""), "{}", err);
+    let err = parse("up 1\nsideways").unwrap_err();
+    assert_eq!(err.to_string(), "day 2, line 2, column 1: Unknown command \"sideways\"");
+    let err = parse("up 1 2").unwrap_err();
+    assert_eq!(err.to_string(), "day 2, line 1, column 6: Unexpected \"2\" after the parameter");
+  }
+
+  #[test]
+  fn reports_bad_parameter_column() {
+    let err = Day02::parse("forward 5\ndown five\n").unwrap_err();
+    assert!(err.to_string().starts_with("day 2, line 2, column 6:"), "{}", err);
+  }
+}
@@ -0,0 +1,159 @@
+//! Day 3: Binary Diagnostic. Picking readings apart bit by bit.
+
+use aoc_common::{Error, Result, Solution};
+
+pub const PUZZLE: &'static str = include_str!("input03");
+pub const SAMPLE: &'static str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+// `line_no` is 1-based, for error reporting
+fn parse(line_no: usize, line: &str, n: u16) -> Result<u16> {
+  let line = line.trim_end();
+  if let Some(col) = line.bytes().position(|b| b != b'0' && b != b'1') {
+    return Err(Error::parse(Day03::DAY, line_no, col + 1, "Expected a binary digit"));
+  }
+  if line.len() != n as usize {
+    return Err(Error::parse(Day03::DAY, line_no, 1,
+      format!("Reading has {} bits, expected {}", line.len(), n)));
+  }
+  Ok(u16::from_str_radix(line, 2).expect("validated binary number"))
+}
+
+/// How many zeros and ones each of the low `n` bits has across `input`,
+/// lowest bit first
+pub fn bit_count(input: &[u16], n: u16) -> Vec<(u16, u16)> {
+  let mut ret = vec![(0, 0); n as usize];
+  for k in input.iter().copied() {
+    for i in 0..n {
+      let bit = (k >> i) & 1;
+      match bit {
+        0 => ret[i as usize].0 += 1,
+        1 => ret[i as usize].1 += 1,
+        _ => ()
+      };
+    }
+  }
+  return ret;
+}
+
+/// The gamma rate (most common bits) times the epsilon rate (least common bits)
+pub fn part_a(input: &[u16], n: u16) -> u32 {
+  let counts = bit_count(input, n);
+  let mut gamma: u16 = 0;
+  let mut epsilon: u16 = 0;
+  for (i, (zero, one)) in counts.into_iter().enumerate() {
+    let i = i as u16;
+    if zero > one {
+      epsilon |= 1 << i;
+    }
+    else if one > zero {
+      gamma |= 1 << i;
+    }
+  }
+  (gamma as u32) * (epsilon as u32)
+}
+
+/// Whether bit `n` is set in at least half of `input`
+pub fn most_common_bit(input: &[u16], n: u16) -> bool {
+  let mut count_0 = 0;
+  let mut count_1 = 0;
+  for s in input.iter().copied() {
+    if ((s >> n) & 1) != 0 {
+      count_1 += 1;
+    } else {
+      count_0 += 1;
+    }
+  }
+  return count_1 >= count_0;
+}
+
+/// Keeps the numbers whose bit `n` is the most common value of that bit,
+/// or the least common one if `invert_common` is set
+pub fn parity_filter(numbers: &mut Vec<u16>, n: u16, invert_common: bool) {
+  let filter = most_common_bit(numbers, n);
+  let filter = (filter ^ invert_common) as u16;
+  let mut kept_idx = 0;
+  let orig_size = numbers.len();
+  for i in 0..orig_size {
+    if ((numbers[i] >> n) & 1) == filter {
+      numbers[kept_idx] = numbers[i];
+      kept_idx += 1;
+    }
+  }
+  log::debug!("Filter = {}, (xor = {:?}) i = {}, old len = {}, new len = {}", filter, invert_common, n, orig_size, kept_idx);
+  numbers.truncate(kept_idx);
+}
+
+/// The oxygen generator rating times the CO2 scrubber rating
+pub fn part_b(mut for_o2: Vec<u16>, n: u16) -> Result<u32> {
+  let mut for_co2 = for_o2.clone();
+  for i in (0..n).rev() {
+    if for_o2.len() > 1 {
+      parity_filter(&mut for_o2, i, false);
+    }
+    if for_co2.len() > 1 {
+      parity_filter(&mut for_co2, i, true);
+    }
+  }
+  if for_o2.len() != 1 {
+    return Err(Error::solve(Day03::DAY, "Couldn't filter out an O2 reading"));
+  }
+  if for_co2.len() != 1 {
+    return Err(Error::solve(Day03::DAY, "Couldn't filter out a CO2 reading"));
+  }
+  Ok((for_o2[0] as u32) * (for_co2[0] as u32))
+}
+
+/// The number of bits in each reading, which is the width of the lines
+pub fn bit_width(input: &str) -> Result<u16> {
+  let width = input.lines().next()
+    .ok_or_else(|| Error::parse(Day03::DAY, 1, 1, "Empty input"))?.trim().len();
+  if width == 0 || width > 16 {
+    return Err(Error::parse(Day03::DAY, 1, 1,
+      format!("Unsupported reading width {}", width)));
+  }
+  Ok(width as u16)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+  const DAY: u8 = 3;
+  // The readings and how many bits wide they are
+  type Input = (Vec<u16>, u16);
+  type A = u32;
+  type B = u32;
+
+  fn parse(input: &str) -> Result<(Vec<u16>, u16)> {
+    let n = bit_width(input)?;
+    let parsed: Result<Vec<u16>> = input.lines().enumerate()
+      .map(|(i, l)| parse(i + 1, l, n)).collect();
+    Ok((parsed?, n))
+  }
+
+  fn part_a((readings, n): &(Vec<u16>, u16)) -> Result<u32> {
+    Ok(part_a(readings, *n))
+  }
+
+  fn part_b((readings, n): &(Vec<u16>, u16)) -> Result<u32> {
+    part_b(readings.clone(), *n)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  aoc_common::sample_tests!(Day03, 198, 230);
+}
@@ -0,0 +1,182 @@
+//! Day 4: Giant Squid. Bingo against a squid, to win or to lose.
+//!
+//! Boards are stored row-major as `SIZE` numbers, with a matching array
+//! of marks.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+/// Boards are N by N
+pub const N: usize = 5;
+pub const SIZE: usize = N * N;
+
+fn parse_number(line_no: usize, line: &str, n: &str) -> Result<u8> {
+  n.trim().parse().map_err(|e| Error::parse(Day04::DAY, line_no, column(line, n),
+    format!("Bad number {:?}: {}", n, e)))
+}
+
+// Line numbers count from 1 for error messages
+fn push_board(boards: &mut Vec<Vec<u8>>, board: Vec<u8>, start_line: usize) -> Result<()> {
+  if board.len() != SIZE {
+    return Err(Error::parse(Day04::DAY, start_line, 1,
+      format!("Board has {} numbers, expected {}", board.len(), SIZE)));
+  }
+  boards.push(board);
+  Ok(())
+}
+
+/// The draws and the boards
+pub fn parse(input: &str) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+  let mut lines = input.lines().enumerate().map(|(i, l)| (i + 1, l));
+  let (_, first_line) = lines.next()
+    .ok_or_else(|| Error::parse(Day04::DAY, 1, 1, "Missing draw numbers"))?;
+  let draws: Result<Vec<u8>> = first_line.split(',')
+    .map(|n| parse_number(1, first_line, n)).collect();
+  let draws = draws?;
+  let split = lines.next();
+  if Some("") != split.map(|(_, l)| l.trim()) {
+    return Err(Error::parse(Day04::DAY, 2, 1, "Missing blank line between numbers and boards"));
+  }
+  let mut boards: Vec<Vec<u8>> = Vec::new();
+  let mut board = Vec::<u8>::new();
+  let mut board_start = 0;
+  for (line_no, line) in lines {
+    if line.trim().is_empty() {
+      if board.len() == 0 {
+        continue;
+      }
+      push_board(&mut boards, board, board_start)?;
+      board = Vec::new();
+    }
+    else {
+      if board.len() == 0 {
+        board_start = line_no;
+      }
+      for n in line.split_ascii_whitespace() {
+        board.push(parse_number(line_no, line, n)?);
+      }
+    }
+  }
+  if board.len() != 0 {
+    push_board(&mut boards, board, board_start)?;
+  }
+  Ok((draws, boards))
+}
+
+
+// Mark off the square labelled `n` on `board`, if any, by setting the corresponding bit in the `marks` array. Return `true` if marking was performed
+pub fn mark(board: &[u8], marks: &mut [bool], n: u8) -> bool {
+  assert!(board.len() == marks.len());
+  for (i, m) in board.iter().zip(marks.iter_mut()) {
+    if *i == n {
+      *m = true;
+      return true;
+    }
+  }
+  return false;
+}
+
+/// Whether a whole row or column is marked
+pub fn has_bingo(marks: &[bool]) -> bool {
+  assert!(marks.len() == N * N);
+  let row_bingo = marks.chunks_exact(N).any(|c| c.iter().copied().all(|x| x));
+  let column_bingo = (0..N).any(|j| (0..N).all(|i| marks[j + N * i]));
+  row_bingo || column_bingo
+}
+
+/// The sum of the numbers that haven't been marked
+pub fn score_unmarked(board: &[u8], marks: &[bool]) -> u64 {
+  board.iter().copied().zip(marks.iter().copied())
+  .filter_map(|(n, m)| if m { None } else { Some (n as u64) })
+  .sum()
+}
+
+/// The score of the first board to win
+pub fn part_a(draws: &[u8], boards: &[Vec<u8>]) -> Result<u64> {
+  let mut marks: Vec<Vec<bool>> = (0..boards.len()).map(|_| vec![false; SIZE]).collect();
+  for drawn in draws.iter().copied() {
+    for (board, marks) in boards.iter().zip(marks.iter_mut()) {
+      if mark(board, marks, drawn) && has_bingo(marks) {
+        return Ok(score_unmarked(board, marks) * (drawn as u64));
+      }
+    }
+  }
+  Err(Error::solve(Day04::DAY, "No one got a bingo"))
+}
+
+/// The score of the last board to win
+pub fn part_b(draws: &[u8], boards: &[Vec<u8>]) -> Result<u64> {
+  let n_boards = boards.len();
+  let mut marks: Vec<Vec<bool>> = (0..n_boards).map(|_| vec![false; SIZE]).collect();
+  let mut already_won = vec![false; n_boards];
+  let mut bingo_count = 0;
+  for d in draws.iter().copied() {    
+    for (already_won, (board, marks)) in already_won.iter_mut().zip(boards.iter().zip(marks.iter_mut())).filter(|t| !*t.0) {
+      if mark(board, marks, d) && has_bingo(marks) {
+        *already_won = true;
+        bingo_count += 1;
+        if bingo_count == n_boards {
+          return Ok(score_unmarked(board, marks) * (d as u64));
+        }
+      }
+    }
+  }
+  Err(Error::solve(Day04::DAY, "Not every board got a bingo"))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  const DAY: u8 = 4;
+  // The draws and the boards
+  type Input = (Vec<u8>, Vec<Vec<u8>>);
+  type A = u64;
+  type B = u64;
+
+  fn parse(input: &str) -> Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_a((draws, boards): &Self::Input) -> Result<u64> {
+    part_a(draws, boards)
+  }
+
+  fn part_b((draws, boards): &Self::Input) -> Result<u64> {
+    part_b(draws, boards)
+  }
+}
+
+pub const PUZZLE: &'static str = include_str!("input04");
+
+pub const SAMPLE: &'static str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11 0
+ 8  2 23  4 24
+21 9 14 16 7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24 4
+14 21 16 12 6
+
+14 21 17 24 4
+10 16 15  9 19
+18 8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  aoc_common::sample_tests!(Day04, 4512, 1924);
+
+  #[test]
+  fn no_bingo_is_an_error() {
+    let (_, boards) = SAMPLE.split_once('\n').unwrap();
+    let input = Day04::parse(&format!("7,4,9\n{}", boards)).unwrap();
+    assert!(Day04::part_a(&input).is_err());
+  }
+}
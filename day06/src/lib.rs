@@ -0,0 +1,97 @@
+//! Day 6: Lanternfish. Counting an exponentially growing school of fish.
+//!
+//! The school is tracked as how many fish have each timer value, `0..=8`.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+pub const ITERATIONS_A: usize = 80;
+pub const ITERATIONS_B: usize = 256;
+pub const PUZZLE: &'static str = include_str!("input06");
+pub const SAMPLE: &'static str = "3,4,3,1,2";
+
+/// The comma-separated timers of the initial fish
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let line = input.trim();
+    line.split(',').map(|l| {
+        let err = |msg| Error::parse(Day06::DAY, 1, column(line, l), msg);
+        let timer: usize = l.trim().parse().map_err(|_| err(format!("{:?} failed to parse", l)))?;
+        if timer > 8 {
+            return Err(err(format!("Timer {} is longer than 8 days", timer)));
+        }
+        Ok(timer)
+    }).collect()
+}
+
+/// Counts the fish with each timer value
+pub fn preprocess(raw_start_state: &[usize]) -> [usize; 9] {
+    let mut ret = [0; 9];
+    for i in raw_start_state.iter().copied() {
+        ret[i] += 1;
+    }
+    return ret
+}
+
+/// Advances one day: timers count down, and fish at 0 reset to 6 and
+/// spawn a new fish at 8
+pub fn step(state: &mut [usize; 9]) {
+    state.rotate_left(1);
+    state[6] += state[8];
+}
+
+pub fn total_fish(state: &[usize; 9]) -> usize {
+    state.iter().sum()
+}
+
+/// How many fish there are after `iterations` days
+pub fn solve(raw_input: &[usize], iterations: usize) -> usize {
+    let mut state = preprocess(raw_input);
+    for _ in 0..iterations {
+        step(&mut state);
+    }
+    total_fish(&state)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<usize>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        parse(input)
+    }
+
+    fn part_a(input: &Vec<usize>) -> Result<usize> {
+        Ok(solve(input, ITERATIONS_A))
+    }
+
+    fn part_b(input: &Vec<usize>) -> Result<usize> {
+        Ok(solve(input, ITERATIONS_B))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_ages_fish() {
+        let mut state = preprocess(&[3, 4, 3, 1, 2]);
+        assert_eq!(state, [0, 1, 1, 2, 1, 0, 0, 0, 0]);
+        step(&mut state);
+        assert_eq!(state, [1, 1, 2, 1, 0, 0, 0, 0, 0]);
+        // The fish at 0 resets to 6 and spawns a new one at 8
+        step(&mut state);
+        assert_eq!(state, [1, 2, 1, 0, 0, 0, 1, 0, 1]);
+        assert_eq!(total_fish(&state), 6);
+    }
+
+    #[test]
+    fn sample_after_18_days() {
+        assert_eq!(solve(&parse(SAMPLE).unwrap(), 18), 26);
+    }
+
+    aoc_common::sample_tests!(Day06, 5934, 26984457539u64);
+}
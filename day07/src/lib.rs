@@ -0,0 +1,81 @@
+//! Day 7: The Treachery of Whales. Lining up crab submarines for the least fuel.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+pub const PUZZLE: &'static str = include_str!("input07");
+pub const SAMPLE: &'static str = "16,1,2,0,4,2,7,1,2,14";
+
+/// The comma-separated horizontal positions of the crabs
+pub fn parse(input: &str) -> Result<Vec<i32>> {
+    let line = input.trim();
+    line.split(',').map(|l| l.trim().parse().map_err(|e| Error::parse(Day07::DAY, 1, column(line, l),
+        format!("{:?} is not a position: {}", l, e)))).collect()
+}
+
+/// Fuel to move every crab to `y` when each step costs 1
+pub fn l1_distance(xi: &[i32], y: i32) -> i32 {
+    xi.iter().copied().map(move |x| (x - y).abs()).sum()
+}
+
+#[inline]
+fn part_b_metric(x: i32, y: i32) -> i32 {
+    let dist = (x - y).abs();
+    ((dist) * (dist + 1)) / 2
+}
+
+/// Fuel to move every crab to `y` when each step costs one more than the last
+pub fn part_b_distance(xi: &[i32], y: i32) -> i32 {
+    xi.iter().copied().map(move |x| part_b_metric(x, y)).sum()
+}
+
+/// The least fuel needed to line up, found by walking downhill from
+/// the average position. `distance` must be convex in its target.
+pub fn solve(input: &[i32], distance: fn(&[i32], i32) -> i32) -> i32 {
+    let average = input.iter().sum::<i32>() / (input.len() as i32);
+    let mut candidate = average;
+    let mut candidate_dist = distance(input, candidate);
+    loop {
+        let left_cand = candidate - 1;
+        let right_cand = candidate + 1;
+        let left_cand_dist = distance(input, left_cand);
+        let right_cand_dist = distance(input, right_cand);
+        if left_cand_dist < candidate_dist {
+            candidate = left_cand;
+            candidate_dist = left_cand_dist;
+        } else if right_cand_dist < candidate_dist {
+            candidate = right_cand;
+            candidate_dist = right_cand_dist;
+        } else { // We've converged
+            break;
+        }
+    }
+    candidate_dist
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<i32>;
+    type A = i32;
+    type B = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        parse(input)
+    }
+
+    fn part_a(input: &Vec<i32>) -> Result<i32> {
+        Ok(solve(input, l1_distance))
+    }
+
+    fn part_b(input: &Vec<i32>) -> Result<i32> {
+        Ok(solve(input, part_b_distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day07, 37, 168);
+}
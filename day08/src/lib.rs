@@ -0,0 +1,273 @@
+//! Day 8: Seven Segment Search. Unscrambling the wiring of seven-segment displays.
+//!
+//! Segments are numbered 0 to 6 for `a` to `g`, and a lit digit is the
+//! set of its segments.
+
+use aoc_common::{error::column, Error, Result, Solution};
+use lazy_static::lazy_static;
+use itertools::Itertools;
+
+use std::collections::HashMap;
+use std::collections::BTreeSet;
+
+use std::iter::FromIterator;
+
+lazy_static! {
+    static ref SEGMENTS: [BTreeSet<usize>; 10] = [
+        BTreeSet::from_iter([0, 1, 2, 4, 5, 6]),
+        BTreeSet::from_iter([2, 5]),
+        BTreeSet::from_iter([0, 2, 3, 4, 6]),
+        BTreeSet::from_iter([0, 2, 3, 5, 6]),
+        BTreeSet::from_iter([1, 2, 3, 5]),
+        BTreeSet::from_iter([0, 1, 3, 5, 6]),
+        BTreeSet::from_iter([0, 1, 3, 4, 5, 6]),
+        BTreeSet::from_iter([0, 2, 5]),
+        BTreeSet::from_iter([0, 1, 2, 3, 4, 5, 6]),
+        BTreeSet::from_iter([0, 1, 2, 3, 5, 6]),
+    ];
+    static ref DIGITS: HashMap<BTreeSet<usize>, usize> = {
+        SEGMENTS.iter().enumerate().map(|(i, s)| (s.clone(), i))
+            .collect()
+    };
+    static ref DIGITS_BY_LEN: [Vec<usize>; 8] = {
+        [vec![], vec![], vec![1], vec![7],
+            vec![4], vec![2, 3, 5],
+            vec![0, 6, 9], vec![8]]
+    };
+}
+
+/// A partial mapping between scrambled wires and the segments they drive
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Perm {
+    to_seg: [Option<usize>; 10],
+    to_scrambled: [Option<usize>; 10],
+}
+
+impl Perm {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Insert map a <-> b only if a is not mapped to any b'
+    // and there is no a' mapped to be.
+    // This includes duplicate entries of the same mapping
+    pub fn try_insert(&mut self, scrambled: usize, segment: usize) -> bool {
+        if self.to_seg[scrambled].is_some()
+                || self.to_scrambled[segment].is_some() {
+            false
+        } else {
+            self.to_seg[scrambled] = Some(segment);
+            self.to_scrambled[segment] = Some(scrambled);
+            true
+        }
+    }
+
+    // Map all defined members of input through the permutation
+    // Ignore those inputs with no defined value
+    pub fn unscramble(&self, input: &BTreeSet<usize>) -> BTreeSet<usize> {
+        input.iter().copied().filter_map(|i| self.to_seg[i]).collect()
+    }
+
+    pub fn assigned(&self, segments: &BTreeSet<usize>) -> BTreeSet<usize> {
+        segments.iter().copied().filter_map(|i| self.to_scrambled[i]).collect()
+    }
+
+    // Removes the mapping segment <-> scrambled
+    // Panics if the mapping was not previously inserted
+    pub fn unmap(&mut self, scrambled: usize, segment: usize) {
+        self.to_seg[scrambled] = None;
+        self.to_scrambled[segment] = None;
+    }
+}
+
+fn parse_seven_segment(line_no: usize, line: &str, word: &str) -> Result<BTreeSet<usize>> {
+    let mut ret = BTreeSet::new();
+    for (i, x) in word.bytes().enumerate() {
+        if !(b'a'..=b'g').contains(&x) || !ret.insert((x - b'a') as usize) {
+            return Err(Error::parse(Day08::DAY, line_no, column(line, word) + i,
+                format!("Bad segment {:?} in {:?}", x as char, word)));
+        }
+    }
+    Ok(ret)
+}
+
+// The ten scrambled example digits and the four scrambled digits to decode
+pub type Entry = (Vec<BTreeSet<usize>>, Vec<BTreeSet<usize>>);
+
+fn parse(line_no: usize, line: &str) -> Result<Entry> {
+    let (examples_str, tests_str) = line.split_once('|')
+        .ok_or_else(|| Error::parse(Day08::DAY, line_no, 1, "Missing '|' separator"))?;
+    let mut examples = Vec::with_capacity(10);
+    for word in examples_str.split_whitespace() {
+        let digit = parse_seven_segment(line_no, line, word)?;
+        if examples.contains(&digit) {
+            return Err(Error::parse(Day08::DAY, line_no, column(line, word),
+                format!("Example {:?} is the same digit as an earlier one", word)));
+        }
+        examples.push(digit);
+    }
+    if examples.len() != 10 {
+        return Err(Error::parse(Day08::DAY, line_no, 1,
+            format!("Expected 10 example digits, got {}", examples.len())));
+    }
+    let tests: Result<Vec<_>> = tests_str.split_whitespace()
+        .map(|w| parse_seven_segment(line_no, line, w)).collect();
+    let tests = tests?;
+    if tests.len() != 4 {
+        return Err(Error::parse(Day08::DAY, line_no, column(line, tests_str),
+            format!("Expected 4 digits to decode, got {}", tests.len())));
+    }
+    Ok((examples, tests))
+}
+
+fn solve(examples: &[BTreeSet<usize>], perm: &mut Perm) -> bool {
+    if examples.len() == 0 {
+        return true;
+    }
+    let (scrambled, cont) = examples.split_first().unwrap();
+    let already_mapped = perm.unscramble(scrambled);
+    for digit in DIGITS_BY_LEN[scrambled.len()].iter().copied() {
+        let segments = &SEGMENTS[digit];
+        let assigned = perm.assigned(segments);
+        if already_mapped.len() != assigned.len() {
+            continue;
+        }
+        if !segments.is_superset(&already_mapped) {
+            continue;
+        }
+        if &already_mapped == segments {
+            return solve(cont, perm);
+        }
+        for segments_perm in segments.difference(&already_mapped).copied()
+            .permutations(segments.len() - already_mapped.len()) {
+            let mut stopped_at: Option<usize> = None;
+            for (i, (a, b)) in scrambled.difference(&assigned).copied()
+                .zip(segments_perm.iter().copied()).enumerate() {
+                if !perm.try_insert(a, b) {
+                    stopped_at = Some(i);
+                    break;
+                }
+            }
+            if stopped_at.is_none() && solve(cont, perm) {
+                return true;
+            } else {
+                let stop = stopped_at.unwrap_or(scrambled.len());
+                for (a, b) in scrambled.difference(&assigned).copied()
+                    .zip(segments_perm.iter().copied()).take(stop) {
+                    perm.unmap(a, b);
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Finds the wiring that turns the ten scrambled examples into the ten digits
+pub fn solve_examples(mut examples: Vec<BTreeSet<usize>>) -> Option<Perm> {
+    let mut ret = Perm::new();
+    examples.sort_by_key(|x| x.len());
+    if solve(&examples, &mut ret) { Some(ret) } else { None }
+}
+
+/// Reads scrambled digits through `perm`, failing if one isn't a digit
+pub fn decode(tests: &[BTreeSet<usize>], perm: &Perm) -> Option<Vec<usize>> {
+    tests.iter().map(
+        |scr| DIGITS.get(&perm.unscramble(scr)).copied())
+    .collect()
+}
+
+pub const PUZZLE: &'static str = include_str!("input08");
+pub const SAMPLE: &'static str = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n";
+
+/// The four output digits of each entry
+pub fn decode_entries(entries: &[Entry]) -> Result<Vec<Vec<usize>>> {
+    entries.iter().enumerate().map(|(i, (examples, tests))| {
+        let perm = solve_examples(examples.clone()).ok_or_else(||
+            Error::solve_at(Day08::DAY, i + 1, "Couldn't solve examples"))?;
+        decode(tests, &perm).ok_or_else(||
+            Error::solve_at(Day08::DAY, i + 1, "Digits to decode don't match the examples"))
+    }).collect()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Vec<Entry>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Vec<Entry>> {
+        input_str.lines().enumerate().map(|(i, l)| parse(i + 1, l)).collect()
+    }
+
+    fn part_a(entries: &Vec<Entry>) -> Result<usize> {
+        Ok(decode_entries(entries)?.iter()
+            .map(|i| i.iter().copied()
+                .filter(|&e| e == 1 || e == 4 || e == 7 || e == 8 ).count())
+            .sum())
+    }
+
+    fn part_b(entries: &Vec<Entry>) -> Result<usize> {
+        Ok(decode_entries(entries)?.iter()
+            .map(|i| i[3] + 10 * i[2] + 100 * i[1] + 1000 * i[0])
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE_SAMPLE: &'static str =
+"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn perm_rejects_conflicting_mappings() {
+        let mut perm = Perm::new();
+        assert!(perm.try_insert(0, 2));
+        // Same mapping twice, scrambled already used, segment already used
+        assert!(!perm.try_insert(0, 2));
+        assert!(!perm.try_insert(0, 3));
+        assert!(!perm.try_insert(1, 2));
+        assert!(perm.try_insert(1, 5));
+        assert_eq!(perm.unscramble(&BTreeSet::from_iter([0, 1, 6])), BTreeSet::from_iter([2, 5]));
+        assert_eq!(perm.assigned(&SEGMENTS[1]), BTreeSet::from_iter([0, 1]));
+    }
+
+    #[test]
+    fn perm_unmap_frees_both_sides() {
+        let mut perm = Perm::new();
+        assert!(perm.try_insert(0, 2));
+        perm.unmap(0, 2);
+        assert_eq!(perm, Perm::new());
+        assert!(perm.try_insert(1, 2));
+        assert!(perm.try_insert(0, 3));
+    }
+
+    #[test]
+    fn large_sample() {
+        let input = Day08::parse(LARGE_SAMPLE).unwrap();
+        assert_eq!(Day08::part_a(&input).unwrap(), 26);
+        assert_eq!(Day08::part_b(&input).unwrap(), 61229);
+    }
+
+    aoc_common::sample_tests!(Day08, 0, 5353);
+
+    #[test]
+    fn requires_ten_distinct_examples() {
+        let err = Day08::parse(&format!("{}| ab ab ab ab", "ab ".repeat(10))).unwrap_err();
+        assert_eq!(err.to_string(), "day 8, line 1, column 4: Example \"ab\" is the same digit as an earlier one");
+        let err = Day08::parse("ab abc abcd | ab ab ab ab").unwrap_err();
+        assert_eq!(err.to_string(), "day 8, line 1, column 1: Expected 10 example digits, got 3");
+    }
+}
@@ -0,0 +1,93 @@
+//! Day 9: Smoke Basin. Low points and basins in a height map.
+
+use aoc_common::grid::{Coord, Grid};
+use aoc_common::{Error, Result, Solution};
+
+use std::collections::HashSet;
+
+pub const PUZZLE: &'static str = include_str!("input09");
+pub const SAMPLE: &'static str =
+"2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+/// A rectangular grid of single-digit heights
+pub fn parse(input: &str) -> Result<Grid<u8>> {
+    Grid::parse_digits(Day09::DAY, input)
+}
+
+/// The total risk level of the low points, and where they are
+pub fn part_a(input: &Grid<u8>) -> (i32, Vec<Coord>) {
+    let mut ret = 0;
+    let mut low_points = vec![];
+    for (coord, &here) in input.iter() {
+        // Edges act like walls taller than anything in the input
+        if input.neighbors4(coord).all(|n| here < input[n]) {
+            low_points.push(coord);
+            ret += (1 + here) as i32;
+        }
+    }
+    (ret, low_points)
+}
+
+/// Adds every point of the basin containing `coord` to `ret`.
+/// Basins are bounded by heights of 9.
+pub fn basin(input: &Grid<u8>, coord: Coord, ret: &mut HashSet<Coord>) {
+    if input[coord] == 9 {
+        return;
+    }
+    ret.insert(coord);
+    for neighbor in input.neighbors4(coord) {
+        if !ret.contains(&neighbor) {
+            basin(input, neighbor, ret);
+        }
+    }
+}
+
+/// The product of the sizes of the three largest basins
+pub fn part_b(input: &Grid<u8>, low_points: &[Coord]) -> Result<usize> {
+    let mut basins = Vec::new();
+    for coord in low_points.iter().copied() {
+        let mut component = HashSet::new();
+        basin(input, coord, &mut component);
+        basins.push(component);
+    }
+    basins.sort_unstable_by_key(|b| b.len());
+    let n_basins = basins.len();
+    if n_basins < 3 {
+        return Err(Error::solve(Day09::DAY, format!("Only {} basins, need 3", n_basins)));
+    }
+    Ok(basins[n_basins-3..].iter().map(|s| s.len()).product())
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Grid<u8>;
+    type A = i32;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Grid<u8>> {
+        parse(input_str)
+    }
+
+    fn part_a(input: &Grid<u8>) -> Result<i32> {
+        Ok(part_a(input).0)
+    }
+
+    fn part_b(input: &Grid<u8>) -> Result<usize> {
+        let (_, low_points) = part_a(input);
+        part_b(input, &low_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day09, 15, 1134);
+}
@@ -0,0 +1,184 @@
+//! Day 10: Syntax Scoring. Corrupted and incomplete bracket sequences.
+
+use aoc_common::{Error, Result, Solution};
+
+/// The four kinds of bracket
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum Tag {
+    Paren, Square, Curly, Angle,
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum Token {
+    Open(Tag),
+    Close(Tag),
+}
+
+pub fn lex(b: u8) -> Option<Token> {
+    use Token::*;
+    use Tag::*;
+    match b {
+        b'(' => Some(Open(Paren)),
+        b')' => Some(Close(Paren)),
+        b'[' => Some(Open(Square)),
+        b']' => Some(Close(Square)),
+        b'{' => Some(Open(Curly)),
+        b'}' => Some(Close(Curly)),
+        b'<' => Some(Open(Angle)),
+        b'>' => Some(Close(Angle)),
+        _ => None,
+    }
+}
+
+/// One line of brackets per chunk sequence
+pub fn parse(input: &str) -> Result<Vec<Vec<Token>>> {
+    input.lines().enumerate().map(|(i, l)|
+        l.trim_end().bytes().enumerate().map(|(j, b)| lex(b).ok_or_else(||
+            Error::parse(Day10::DAY, i + 1, j + 1,
+                format!("{:?} is not a bracket", b as char))))
+        .collect()).collect()
+}
+
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub enum WalkResult {
+    /// The first closing bracket that didn't match its opener
+    Mismatch(Tag),
+    /// The brackets left open at the end, innermost last
+    Unclosed(Vec<Tag>),
+    /// Balanced, or closing more than was opened
+    Other,
+}
+
+/// Matches brackets along a line until the first corrupted one
+pub fn walk(line: &[Token]) -> WalkResult {
+    let mut stack = Vec::new();
+    for t in line.iter().copied() {
+        match t {
+            Token::Open(tag) => {
+                stack.push(tag);
+            },
+            Token::Close(tag) => {
+                if let Some(matched) = stack.pop() {
+                    if tag != matched {
+                        return WalkResult::Mismatch(tag);
+                    }
+                } else {
+                    return WalkResult::Other;
+                }
+            }
+        }
+    }
+    if stack.len() > 0 {
+        WalkResult::Unclosed(stack)
+    } else {
+        WalkResult::Other
+    }
+}
+
+/// Syntax error score of a mismatched closer
+pub fn score(tag: Tag) -> u64 {
+    use Tag::*;
+    match tag {
+        Paren => 3,
+        Square => 57,
+        Curly => 1197,
+        Angle => 25137,
+    }
+}
+
+/// Autocomplete score of one closer
+pub fn score_b(tag: Tag) -> u64 {
+    use Tag::*;
+    match tag {
+        Paren => 1,
+        Square => 2,
+        Curly => 3,
+        Angle => 4,
+    }
+}
+
+/// Total syntax error score of the corrupted lines
+pub fn part_a(input: &[Vec<Token>]) -> u64 {
+    let mut ret = 0;
+    for line in input {
+        if let WalkResult::Mismatch(tag) = walk(line) {
+            ret += score(tag);
+        }
+    }
+    ret
+}
+
+/// Median autocomplete score of the incomplete lines
+pub fn part_b(input: &[Vec<Token>]) -> Result<u64> {
+    let mut results = Vec::new();
+    for line in input {
+        if let WalkResult::Unclosed(tags) = walk(line) {
+            results.push(tags.into_iter().rev().fold(0, |s, t| score_b(t) + 5 * s))
+        }
+    }
+    if results.len() == 0 {
+        return Err(Error::solve(Day10::DAY, "No incomplete lines"));
+    }
+    results.sort_unstable();
+    Ok(results[results.len() / 2])
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Vec<Token>>;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<Vec<Vec<Token>>> {
+        parse(input_str)
+    }
+
+    fn part_a(input: &Vec<Vec<Token>>) -> Result<u64> {
+        Ok(part_a(input))
+    }
+
+    fn part_b(input: &Vec<Vec<Token>>) -> Result<u64> {
+        part_b(input)
+    }
+}
+
+pub const PUZZLE: &'static str = include_str!("input10");
+pub const SAMPLE: &'static str =
+"[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk_str(line: &str) -> WalkResult {
+        walk(&parse(line).unwrap()[0])
+    }
+
+    #[test]
+    fn walk_finds_first_mismatch() {
+        assert_eq!(walk_str("{([(<{}[<>[]}>{[]{[(<()>"), WalkResult::Mismatch(Tag::Curly));
+        assert_eq!(walk_str("[[<[([]))<([[{}[[()]]]"), WalkResult::Mismatch(Tag::Paren));
+        assert_eq!(walk_str("<{([([[(<>()){}]>(<<{{"), WalkResult::Mismatch(Tag::Angle));
+    }
+
+    #[test]
+    fn walk_returns_unclosed_stack() {
+        use Tag::*;
+        assert_eq!(walk_str("[({(<(())[]>[[{[]{<()<>>"),
+            WalkResult::Unclosed(vec![Square, Paren, Curly, Paren, Square, Square, Curly, Curly]));
+        assert_eq!(walk_str("[<>({}){}[([])<>]]"), WalkResult::Other);
+    }
+
+    aoc_common::sample_tests!(Day10, 26397, 288957);
+}
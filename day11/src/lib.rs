@@ -0,0 +1,145 @@
+//! Day 11: Dumbo Octopus. Flashes spreading across a grid of energy levels.
+
+use aoc_common::grid::{Coord, Grid, ALL_AROUND};
+use aoc_common::{Result, Solution};
+
+use fxhash::FxHashSet;
+
+type CoordSet = FxHashSet<Coord>;
+
+/// The grid of single-digit energy levels
+pub fn parse(input: &str) -> Result<Grid<u8>> {
+    Grid::parse_digits(Day11::DAY, input)
+}
+
+fn get_flashers(arr: &Grid<u8>, to_test: &CoordSet, flashed: &CoordSet,
+        new_flashes: &mut CoordSet) {
+    for coord in to_test.iter().copied() {
+        if arr[coord] > 9 && !flashed.contains(&coord) {
+            new_flashes.insert(coord);
+        }
+    }
+}
+
+fn propagate_flash(coord: Coord, arr: &mut Grid<u8>,
+        flashed: &CoordSet, to_test: &mut CoordSet) {
+    for d in ALL_AROUND {
+        if let Some(neighbor) = arr.offset(coord, d) {
+            arr[neighbor] += 1;
+            if arr[neighbor] > 9 && !flashed.contains(&neighbor) {
+                to_test.insert(neighbor);
+            }
+        }
+    }
+}
+
+// Invariant: new_flashes starts and ends empty
+// to_test came from the previous iteration
+fn small_step(arr: &mut Grid<u8>, flashed: &mut CoordSet,
+        to_test: &mut CoordSet, new_flashes: &mut CoordSet) {
+    get_flashers(arr, to_test, flashed, new_flashes);
+    to_test.clear();
+    for coord in new_flashes.iter().copied() {
+        propagate_flash(coord, arr, flashed, to_test);
+    }
+    flashed.extend(new_flashes.drain());
+}
+
+fn large_step(arr: &mut Grid<u8>, flashed: &mut CoordSet,
+    to_test: &mut CoordSet, new_flashes: &mut CoordSet) {
+
+    for (coord, e) in arr.iter_mut() {
+        *e += 1;
+        if *e > 9 {
+            to_test.insert(coord);
+        }
+    }
+
+    while !to_test.is_empty() {
+        small_step(arr, flashed, to_test, new_flashes);
+    }
+}
+
+fn new_coord_set() -> CoordSet {
+    CoordSet::with_hasher(fxhash::FxBuildHasher::default())
+}
+
+pub const STEPS_A: usize = 100;
+/// How many flashes there are in the first `STEPS_A` steps
+pub fn part_a(arr: &mut Grid<u8>) -> usize {
+    let mut ret = 0;
+    let mut flashed = new_coord_set();
+    let mut new_flashes = new_coord_set();
+    let mut to_test = new_coord_set();
+
+    for i in 0..STEPS_A {
+        log::trace!("Before step {}\n{}", i, arr);
+        large_step(arr, &mut flashed, &mut to_test,
+                &mut new_flashes);
+        ret += flashed.len();
+        for coord in flashed.drain() {
+            arr[coord] = 0;
+        }
+    }
+    ret
+}
+
+/// The number of steps until every octopus flashes at once
+pub fn part_b(arr: &mut Grid<u8>) -> usize {
+    let mut ret = 0;
+    let mut flashed = new_coord_set();
+    let mut new_flashes = new_coord_set();
+    let mut to_test = new_coord_set();
+    loop {
+        if arr.iter().all(|(_, &i)| i == 0) {
+            break ret;
+        }
+        large_step(arr, &mut flashed, &mut to_test,
+                &mut new_flashes);
+        for coord in flashed.drain() {
+            arr[coord] = 0;
+        }
+        ret += 1;
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Grid<u8>;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Grid<u8>> {
+        parse(input_str)
+    }
+
+    fn part_a(input: &Grid<u8>) -> Result<usize> {
+        Ok(part_a(&mut input.clone()))
+    }
+
+    fn part_b(input: &Grid<u8>) -> Result<usize> {
+        Ok(part_b(&mut input.clone()))
+    }
+}
+
+pub const PUZZLE: &'static str = include_str!("input11");
+pub const SAMPLE: &'static str =
+"5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day11, 1656, 195);
+}
@@ -0,0 +1,206 @@
+//! Day 12: Passage Pathing. Counting paths through a cave system.
+
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::{Error, Result, Solution};
+
+pub const START: NodeId = 0;
+pub const END: NodeId = 1;
+/// Small caves visited are tracked in a bitmask
+pub const MAX_CAVES: usize = 64;
+
+/// The cave system. `start` is always node `START` and `end` node `END`.
+/// Big caves, named in capitals, can be visited repeatedly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Caves {
+    pub graph: Graph,
+    big: Vec<bool>,
+}
+
+impl Default for Caves {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Caves {
+    pub fn new() -> Self {
+        let mut ret = Caves { graph: Graph::new(), big: vec![] };
+        ret.get_node_idx("start"); // start is node 0
+        ret.get_node_idx("end"); // end is node 1
+        ret
+    }
+
+    fn get_node_idx(&mut self, key: &str) -> NodeId {
+        let idx = self.graph.node(key);
+        if idx == self.big.len() {
+            let first_letter = key.as_bytes()[0];
+            let is_big = first_letter >= b'A' && first_letter <= b'Z';
+            log::debug!("Adding node {} as {} repeatable={}", key, idx, is_big);
+            self.big.push(is_big);
+        }
+        idx
+    }
+
+    pub fn is_big(&self, node: NodeId) -> bool {
+        self.big[node]
+    }
+
+    pub fn add_edge(&mut self, a: &str, b: &str) -> Result<()> {
+        if a.is_empty() || b.is_empty() {
+            return Err(Error::parse(Day12::DAY, 1, 1, "Empty cave name"));
+        }
+        let idx_a = self.get_node_idx(a);
+        let idx_b = self.get_node_idx(b);
+        if self.big.len() > MAX_CAVES {
+            return Err(Error::parse(Day12::DAY, 1, 1,
+                format!("More than {} caves", MAX_CAVES)));
+        }
+        if self.big[idx_a] && self.big[idx_b] {
+            return Err(Error::parse(Day12::DAY, 1, 1,
+                format!("Adding infinite cycle {} <-> {}", a, b)));
+        }
+        self.graph.add_unweighted(idx_a, idx_b);
+        Ok(())
+    }
+}
+
+/// Builds the cave system from its `a-b` edge list
+pub fn create_graph(input_str: &str) -> Result<Caves> {
+    let mut ret = Caves::new();
+    for (i, edge) in input_str.lines().enumerate() {
+        let edge = edge.trim();
+        if edge.is_empty() {
+            continue;
+        }
+        let (a, b) = edge.split_once('-').ok_or_else(||
+            Error::parse(Day12::DAY, i + 1, 1, format!("{:?} is missing a dash", edge)))?;
+        ret.add_edge(a, b).map_err(|e| e.offset_lines(i))?;
+    }
+    Ok(ret)
+}
+
+/// The small caves a path has been through, and whether it has
+/// used up its one second visit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Visits {
+    small: u64,
+    revisited: bool,
+}
+
+fn paths_to_end(caves: &Caves, may_revisit: bool) -> usize {
+    let init = Visits { small: 1 << START, revisited: !may_revisit };
+    caves.graph.count_paths(START, END, init, |visits, node| {
+        let bit = 1 << node;
+        if caves.is_big(node) {
+            Some(*visits)
+        } else if visits.small & bit == 0 {
+            Some(Visits { small: visits.small | bit, ..*visits })
+        } else if node != START && !visits.revisited {
+            Some(Visits { revisited: true, ..*visits })
+        } else {
+            None
+        }
+    })
+}
+
+/// Paths from start to end that visit small caves at most once
+pub fn part_a(caves: &Caves) -> usize {
+    paths_to_end(caves, false)
+}
+
+/// Paths from start to end that may visit one small cave twice
+pub fn part_b(caves: &Caves) -> usize {
+    paths_to_end(caves, true)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Caves;
+    type A = usize;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Caves> {
+        create_graph(input_str)
+    }
+
+    fn part_a(caves: &Caves) -> Result<usize> {
+        Ok(part_a(caves))
+    }
+
+    fn part_b(caves: &Caves) -> Result<usize> {
+        Ok(part_b(caves))
+    }
+}
+
+pub const PUZZLE: &'static str = include_str!("input12");
+pub const SAMPLE: &'static str =
+"fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_SAMPLE: &'static str =
+"start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    const MEDIUM_SAMPLE: &'static str =
+"dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sl
+kj-HN
+kj-dc";
+
+    fn solve(input: &str) -> (usize, usize) {
+        let graph = Day12::parse(input).unwrap();
+        (Day12::part_a(&graph).unwrap(), Day12::part_b(&graph).unwrap())
+    }
+
+    #[test]
+    fn small_sample() {
+        assert_eq!(solve(SMALL_SAMPLE), (10, 36));
+    }
+
+    #[test]
+    fn medium_sample() {
+        assert_eq!(solve(MEDIUM_SAMPLE), (19, 103));
+    }
+
+    aoc_common::sample_tests!(Day12, 226, 3509);
+
+    #[test]
+    fn big_caves_cannot_be_adjacent() {
+        let err = Day12::parse("start-A\nA-B\n").unwrap_err();
+        assert!(err.to_string().starts_with("day 12, line 2,"), "{}", err);
+    }
+}
+
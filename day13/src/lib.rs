@@ -0,0 +1,219 @@
+//! Day 13: Transparent Origami. Folding a sheet of dots into a code.
+
+use aoc_common::{error::column, Error, Grid, Result, Solution};
+use fxhash::FxHashSet;
+
+use std::fmt;
+
+/// The axis a fold line runs perpendicular to
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Axis {
+    X, Y,
+}
+
+/// A dot on the paper
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn update(&self, axis: Axis, p: i32) -> Self {
+        match axis {
+            Axis::X => Self {x: p, y: self.y},
+            Axis::Y => Self {x: self.x, y: p},
+        }
+    }
+
+    pub fn get(&self, axis: Axis) -> i32 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+        }
+    }
+}
+
+impl std::str::FromStr for Point {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let coord = |c: &str| c.parse().map_err(|e| Error::parse(Day13::DAY, 1, column(s, c),
+            format!("Bad coordinate {:?}: {}", c, e)));
+        match s.split_once(',') {
+            Some((x, y)) => {
+                Ok(Point::new(coord(x)?, coord(y)?))
+            },
+            None => {
+                Err(Error::parse(Day13::DAY, 1, 1, "No comma in point"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// A `fold along axis=coord` instruction
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fold {
+    pub axis: Axis,
+    pub coord: i32,
+}
+
+impl Fold {
+    pub fn new(axis: Axis, coord: i32) -> Self {
+        Self { axis, coord }
+    }
+}
+
+/// Parses one `fold along` line
+pub fn parse_fold(line_no: usize, line: &str) -> Result<Fold> {
+    let (dir, coord) = line.split_once('=')
+        .ok_or_else(|| Error::parse(Day13::DAY, line_no, 1, "No = in fold"))?;
+    let dir_col = column(line, dir) + dir.len().saturating_sub(1);
+    let coord = coord.parse().map_err(|e| Error::parse(Day13::DAY, line_no,
+        column(line, coord), format!("Bad fold coordinate {:?}: {}", coord, e)))?;
+    match dir.bytes().last() {
+        Some(b'x') => Ok(Fold::new(Axis::X, coord)),
+        Some(b'y') => Ok(Fold::new(Axis::Y, coord)),
+        b => Err(Error::parse(Day13::DAY, line_no, dir_col,
+            format!("Unexpected fold axis {:?}", b.map(char::from)))),
+    }
+}
+
+/// The dots and the fold instructions
+pub fn parse(input_str: &str) -> Result<(FxHashSet<Point>, Vec<Fold>)> {
+    let (points, folds) = input_str.split_once("\n\n")
+        .ok_or_else(|| Error::parse(Day13::DAY, 1, 1, "No split point before folds"))?;
+    // The blank line sits between the two sections
+    let fold_start = points.lines().count() + 2;
+    let points: Result<FxHashSet<Point>> =
+        points.lines().enumerate()
+            .map(|(i, l)| l.trim().parse().map_err(|e: Error| e.offset_lines(i)))
+            .collect();
+    let points = points?;
+    let folds: Result<Vec<Fold>> =
+        folds.lines().enumerate().filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| parse_fold(fold_start + i, l.trim())).collect();
+    let folds = folds?;
+    Ok((points, folds))
+}
+
+/// Folds the lower or right half of the paper onto the rest
+pub fn apply_fold(input: &FxHashSet<Point>, fold: Fold) -> FxHashSet<Point> {
+    let Fold {axis, coord} = fold;
+    // Fold accoss y=N works on the x coordinate and vice versa
+    input.iter().copied().map(move |p| {
+        if p.get(axis) >= coord {
+            let dist = p.get(axis) - coord;
+            p.update(axis, coord - dist)
+        } else {
+            p
+        }
+    }).collect()
+}
+
+/// Dots left after the first fold
+pub fn part_a(points: &FxHashSet<Point>, folds: &[Fold]) -> Result<usize> {
+    let first = folds.first()
+        .ok_or_else(|| Error::solve(Day13::DAY, "No folds to apply"))?;
+    let new_points = apply_fold(points, *first);
+    Ok(new_points.len())
+}
+
+/// Draws the dots as `#` on a background of `.`, from the origin unless
+/// folds have left dots left of or above it, in which case from those
+pub fn render(points: FxHashSet<Point>) -> String {
+    let range = |axis| {
+        let coords = points.iter().map(|p| p.get(axis));
+        (coords.clone().min().unwrap_or(0).min(0), coords.max().unwrap_or(0).max(0))
+    };
+    let (min_x, max_x) = range(Axis::X);
+    let (min_y, max_y) = range(Axis::Y);
+    Grid::from_fn((max_y - min_y) as usize + 1, (max_x - min_x) as usize + 1,
+        |(y, x)| points.contains(&Point::new(min_x + x as i32, min_y + y as i32))).render()
+}
+
+/// The dots after every fold
+pub fn part_b(points: FxHashSet<Point>, folds: &[Fold]) -> FxHashSet<Point> {
+    folds.iter().copied().fold(points, |p, f| apply_fold(&p, f))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = (FxHashSet<Point>, Vec<Fold>);
+    type A = usize;
+    type B = String;
+
+    fn parse(input_str: &str) -> Result<Self::Input> {
+        parse(input_str)
+    }
+
+    fn part_a((points, folds): &Self::Input) -> Result<usize> {
+        part_a(points, folds)
+    }
+
+    fn part_b((points, folds): &Self::Input) -> Result<String> {
+        Ok(render(part_b(points.clone(), folds)))
+    }
+}
+
+pub const PUZZLE: &'static str = include_str!("input13");
+pub const SAMPLE: &'static str =
+"6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_reflects_points_past_the_line() {
+        let (points, folds) = parse(SAMPLE).unwrap();
+        assert_eq!(folds, vec![Fold::new(Axis::Y, 7), Fold::new(Axis::X, 5)]);
+        let folded = apply_fold(&points, folds[0]);
+        assert!(folded.contains(&Point::new(0, 0))); // from 0,14
+        assert!(folded.contains(&Point::new(6, 4))); // from 6,10
+        assert!(folded.contains(&Point::new(3, 4))); // unmoved
+        assert!(folded.iter().all(|p| p.get(Axis::Y) < 7));
+        let folded = apply_fold(&folded, folds[1]);
+        assert_eq!(folded.len(), 16);
+        assert!(folded.iter().all(|p| p.get(Axis::X) < 5));
+    }
+
+    aoc_common::sample_tests!(Day13, 17, "#####\n#...#\n#...#\n#...#\n#####");
+
+    #[test]
+    fn renders_negative_points() {
+        let input = Day13::parse("-1,-2\n-3,-4\n\nfold along y=1").unwrap();
+        assert_eq!(Day13::part_b(&input).unwrap(), "#...\n....\n..#.\n....\n....");
+    }
+}
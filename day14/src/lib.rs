@@ -0,0 +1,132 @@
+//! Day 14: Extended Polymerization. Pair insertion, counted by pairs.
+
+use aoc_common::{Error, Result, Solution};
+use fxhash::FxHashMap;
+
+pub const PUZZLE: &'static str = include_str!("input14");
+pub const SAMPLE: &'static str =
+"NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+/// Each pair maps to the two pairs its insertion produces
+pub type PolyRules = FxHashMap<(u8, u8), ((u8, u8), (u8, u8))>;
+/// How often each adjacent pair of elements occurs
+pub type Clusters = FxHashMap<(u8, u8), usize>;
+
+/// The template's pair counts, the rules and the template's last element
+pub fn parse(input: &str) -> Result<(Clusters, PolyRules, u8)> {
+    let (first, rest) = input.split_once("\n\n")
+        .ok_or_else(|| Error::parse(Day14::DAY, 2, 1, "Missing break in input"))?;
+    let first = first.trim();
+    let mut init = Clusters::default();
+    for w in first.as_bytes().windows(2) {
+        *init.entry((w[0], w[1])).or_insert(0) += 1;
+    }
+    let input_last = *first.as_bytes().last()
+        .ok_or_else(|| Error::parse(Day14::DAY, 1, 1, "Empty polymer template"))?;
+    let mut rules = PolyRules::default();
+    // Rules start after the template and the blank line
+    for (i, line) in rest.lines().enumerate() {
+        let line_no = i + 3;
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        let bytes = line.as_bytes();
+        if bytes.len() != 7 || &bytes[2..6] != b" -> " {
+            return Err(Error::parse(Day14::DAY, line_no, 1,
+                format!("Expected rule of the form AB -> C, got {:?}", line)));
+        }
+        let left = bytes[0];
+        let right = bytes[1];
+        let mid = bytes[6];
+        rules.insert((left, right), ((left, mid), (mid, right)));
+    }
+    Ok((init, rules, input_last))
+}
+
+/// One round of insertions
+pub fn step(prev: Clusters, rules: &PolyRules) -> Clusters {
+    let mut ret = Clusters::with_capacity_and_hasher(prev.len(),
+        fxhash::FxBuildHasher::default());
+    for (pair, size) in prev {
+        if let Some((l, r)) = rules.get(&pair).copied() {
+            *ret.entry(l).or_insert(0) += size;
+            *ret.entry(r).or_insert(0) += size;
+        } else {
+            *ret.entry(pair).or_insert(0) += size;
+        }
+    }
+    ret
+}
+
+
+/// Element counts, given that only the last element ends no pair
+pub fn uncluster(clusters: &Clusters, input_last: u8) -> FxHashMap<u8, usize> {
+    let mut ret = FxHashMap::default();
+    for ((a, _b), n) in clusters {
+        *ret.entry(*a).or_insert(0) += n;
+    }
+    // Account for the fact that the last item doesn't get counted above
+    *ret.entry(input_last).or_insert(0) += 1;
+    ret
+}
+
+pub const A_STEPS: usize = 10;
+pub const B_STEPS: usize = 40;
+
+/// Most common minus least common element count after `steps` rounds
+pub fn solve(mut clusters: Clusters, rules: &PolyRules, input_last: u8, steps: usize) -> usize {
+    for _ in 0..steps {
+        clusters = step(clusters, rules);
+    }
+    let elem_counts = uncluster(&clusters, input_last);
+    let max_count = elem_counts.values().copied().max().expect("Nonempty map");
+    let min_count = elem_counts.values().copied().min().expect("Nonempty map");
+    max_count - min_count
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    // The initial pair counts, the insertion rules, and the last element
+    type Input = (Clusters, PolyRules, u8);
+    type A = usize;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Self::Input> {
+        parse(input_str)
+    }
+
+    fn part_a((initial, rules, input_last): &Self::Input) -> Result<usize> {
+        Ok(solve(initial.clone(), rules, *input_last, A_STEPS))
+    }
+
+    fn part_b((initial, rules, input_last): &Self::Input) -> Result<usize> {
+        Ok(solve(initial.clone(), rules, *input_last, B_STEPS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day14, 1588, 2188189693529u64);
+}
@@ -0,0 +1,83 @@
+//! Day 15: Chiton. Lowest-risk paths through a cave.
+
+use aoc_common::graph::Graph;
+use aoc_common::grid::{Coord, Grid};
+use aoc_common::{Result, Solution};
+
+pub const PUZZLE: &'static str = include_str!("input15");
+pub const SAMPLE: &'static str =
+"1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+/// Connects each cell of the map to its four neighbors, where entering
+/// a cell costs its risk level. Cells are numbered in row-major order.
+pub fn to_graph(map: &Grid<u64>) -> Graph {
+    let index = |(i, j): Coord| j + map.cols() * i;
+    let mut graph = Graph::with_nodes(map.rows() * map.cols());
+    for here in map.coords() {
+        for neighbor in map.neighbors4(here) {
+            graph.add_edge(index(here), index(neighbor), map[neighbor]);
+        }
+    }
+    graph
+}
+
+/// Tiles the map five times each way, raising risk by one per tile
+pub fn extend_map(orig: &Grid<u64>) -> Grid<u64> {
+    let (m, n) = (orig.rows(), orig.cols());
+    Grid::from_fn(m * 5, n * 5, |(i, j)| {
+        let dup = (i / m + j / n) as u64;
+        let new = orig[(i % m, j % n)] + dup;
+        if new > 9 { new - 9 } else { new }
+    })
+}
+
+/// Graphs for the map and for its extended version
+pub fn parse(input: &str) -> Result<(Graph, Graph)> {
+    let map = Grid::parse_digits(Day15::DAY, input)?.map(|&d| d as u64);
+    let graph_a = to_graph(&map);
+    let graph_b = to_graph(&extend_map(&map));
+    Ok((graph_a, graph_b))
+}
+
+/// Lowest total risk from the top left to the bottom right
+pub fn solve(graph: &Graph) -> u64 {
+    graph.shortest_path(0, graph.len() - 1).expect("Grids are connected")
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    // The graphs for the original and the extended map
+    type Input = (Graph, Graph);
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<(Graph, Graph)> {
+        parse(input_str)
+    }
+
+    fn part_a((graph_a, _): &(Graph, Graph)) -> Result<u64> {
+        Ok(solve(graph_a))
+    }
+
+    fn part_b((_, graph_b): &(Graph, Graph)) -> Result<u64> {
+        Ok(solve(graph_b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day15, 40, 315);
+}
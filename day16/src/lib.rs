@@ -0,0 +1,307 @@
+//! Day 16: Packet Decoder. Parsing and evaluating BITS transmissions.
+
+use aoc_common::{Error, Result, Solution};
+
+pub const PUZZLE: &'static str = include_str!("input16");
+pub const SAMPLE: &'static str = "8A004A801A8002F478";
+
+/// Reads a byte buffer a few bits at a time, most significant bit first.
+/// Reads past the end yield zeros, see `exhausted`.
+#[derive(Clone, Debug)]
+pub struct Bitstream {
+    bits: Vec<u8>,
+    idx: usize,
+    consumed: usize,
+    valid_bits: u8,
+}
+
+impl Bitstream {
+    pub fn new(bits: Vec<u8>) -> Self {
+        Self { bits, idx: 0, consumed: 0, valid_bits: 8 }
+    }
+
+    /// The next `n` bits, at most 8, as the low bits of a byte
+    pub fn get(&mut self, n: u8) -> u8 {
+        use std::cmp::Ordering;
+        assert!(n <= 8);
+        match n.cmp(&self.valid_bits) {
+            Ordering::Less => {
+                let new_valid = self.valid_bits - n;
+                let ret = self.bits.get(self.idx).copied().unwrap_or(0) >> new_valid;
+                if let Some(b) = self.bits.get_mut(self.idx) {
+                    *b &= (1 << new_valid) - 1;
+                }
+                self.valid_bits = new_valid;
+                self.consumed += n as usize;
+                ret
+            },
+            Ordering::Equal => {
+                self.valid_bits = 8;
+                self.consumed += n as usize;
+                let ret = self.bits.get(self.idx).copied().unwrap_or(0);
+                if let Some(v) = self.bits.get_mut(self.idx) {
+                    *v = 0;
+                }
+                self.idx += 1;
+                ret
+            },
+            Ordering::Greater => {
+                let in_this_bit = self.valid_bits;
+                let remaining = n - in_this_bit;
+                let this_bit = self.bits.get(self.idx).copied()
+                    .unwrap_or(0) << remaining;
+                self.consumed += in_this_bit as usize;
+                self.valid_bits = 8;
+                if let Some(v) = self.bits.get_mut(self.idx) {
+                    *v = 0;
+                }
+                self.idx += 1;
+                let sequel = self.get(remaining);
+                this_bit | sequel
+            }
+        }
+    }
+
+    /// Bits read so far
+    pub fn consumed(&self) -> usize { self.consumed }
+
+    /// Whether a read has gone past the end of the buffer
+    pub fn exhausted(&self) -> bool { self.consumed > self.bits.len() * 8 }
+
+    /// The 1-based column of the hex digit holding the next bit
+    pub fn column(&self) -> usize { self.consumed / 4 + 1 }
+}
+
+/// Decodes a line of hexadecimal into bits
+pub fn to_bitstream(string: &str) -> Result<Bitstream> {
+    let string = string.trim();
+    let digit = |i: usize, c: char| c.to_digit(16).map(|d| d as u8).ok_or_else(||
+        Error::parse(Day16::DAY, 1, i + 1, format!("{:?} is not a hex digit", c)));
+    let char_vec: Vec<char> = string.chars().collect();
+    let bytes = char_vec.chunks(2).enumerate().map(|(i, c)| {
+        let high = digit(2 * i, c[0])?;
+        let low = digit(2 * i + 1, *c.get(1).unwrap_or(&'0'))?;
+        Ok(low | (high << 4))
+    }).collect::<Result<_>>()?;
+    Ok(Bitstream::new(bytes))
+}
+
+/// A literal value, or an operator (by type ID) over subpackets
+#[derive(Clone, Debug)]
+pub enum PacketType {
+    Literal(u64),
+    Operator { typ: u8, ops: Vec<Packet> },
+}
+
+/// A packet and, through its body, all of its subpackets
+#[derive(Clone, Debug)]
+pub struct Packet {
+    pub version: u8,
+    pub body: PacketType,
+}
+
+/// Reads one packet, with its subpackets, off the front of `bits`
+pub fn parse_packet(bits: &mut Bitstream) -> Result<Packet> {
+    let start = bits.column();
+    let version = bits.get(3);
+    let typ = bits.get(3);
+    if bits.exhausted() {
+        return Err(Error::parse(Day16::DAY, 1, start, "Packet header runs past the end of the transmission"));
+    }
+    if typ == 4 {
+        let mut ret: u64 = 0;
+        loop {
+            let next = bits.get(5);
+            let cont = next >> 4;
+            let value = (next & 0xf) as u64;
+            ret = (ret << 4) | value;
+            if cont == 0 { break; }
+        }
+        if bits.exhausted() {
+            return Err(Error::parse(Day16::DAY, 1, start, "Literal runs past the end of the transmission"));
+        }
+        Ok(Packet { version, body: PacketType::Literal(ret) })
+    } else {
+        let length_type = bits.get(1);
+        match length_type {
+            0 => {
+                let to_consume_hi = bits.get(7) as usize;
+                let to_consume_lo = bits.get(8) as usize;
+                let to_consume = (to_consume_hi << 8) | to_consume_lo;
+                if bits.exhausted() {
+                    return Err(Error::parse(Day16::DAY, 1, start,
+                        "Operator length runs past the end of the transmission"));
+                }
+                let consume_start = bits.consumed();
+                let mut ops = Vec::new();
+                while bits.consumed() - consume_start < to_consume {
+                    if bits.exhausted() {
+                        return Err(Error::parse(Day16::DAY, 1, start,
+                            "Subpackets run past the end of the transmission"));
+                    }
+                    ops.push(parse_packet(bits)?);
+                }
+                make_operator(version, typ, ops, start)
+            },
+            1 => {
+                let n_packets_hi = bits.get(3) as usize;
+                let n_packets_lo = bits.get(8) as usize;
+                let n_packets = (n_packets_hi << 8) | n_packets_lo;
+                if bits.exhausted() {
+                    return Err(Error::parse(Day16::DAY, 1, start,
+                        "Operator length runs past the end of the transmission"));
+                }
+                let ops = (0..n_packets).map(|_| parse_packet(bits)).collect::<Result<_>>()?;
+                make_operator(version, typ, ops, start)
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Comparison operators are only defined on exactly two subpackets
+fn make_operator(version: u8, typ: u8, ops: Vec<Packet>, column: usize) -> Result<Packet> {
+    if (5..=7).contains(&typ) && ops.len() != 2 {
+        return Err(Error::parse(Day16::DAY, 1, column,
+            format!("Comparison packet has {} subpackets, expected 2", ops.len())));
+    }
+    Ok(Packet { version, body: PacketType::Operator { typ, ops }})
+}
+
+/// Sum of the versions of a packet and all its subpackets
+pub fn versions_sum(packet: &Packet) -> u64 {
+    let mut ret = packet.version as u64;
+    match &packet.body {
+        PacketType::Literal(_) => (),
+        PacketType::Operator {typ: _typ, ops} => {
+            for packet in ops.iter() {
+                ret += versions_sum(packet);
+            }
+        }
+    }
+    ret
+}
+
+/// The value of the expression a packet encodes
+pub fn execute(packet: &Packet) -> u64 {
+    match &packet.body {
+        PacketType::Literal(v) => *v,
+        PacketType::Operator { typ, ops } => {
+            match *typ {
+                0 => { ops.iter().map(execute).sum() },
+                1 => { ops.iter().map(execute).product() },
+                2 => { ops.iter().map(execute).min().unwrap_or(0) },
+                3 => { ops.iter().map(execute).max().unwrap_or(0) },
+                4 => panic!("Literal here"),
+                5 => {
+                    let v0 = execute(&ops[0]);
+                    let v1 = execute(&ops[1]);
+                    if v0 > v1 { 1 } else { 0 }
+                },
+                6 => {
+                    let v0 = execute(&ops[0]);
+                    let v1 = execute(&ops[1]);
+                    if v0 < v1 { 1 } else { 0 }
+                },
+                7 => {
+                    let v0 = execute(&ops[0]);
+                    let v1 = execute(&ops[1]);
+                    if v0 == v1 { 1 } else { 0 }
+                },
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Parses the outermost packet of a hexadecimal transmission
+pub fn parse(hex: &str) -> Result<Packet> {
+    if hex.trim().is_empty() {
+        return Err(Error::parse(Day16::DAY, 1, 1, "Empty transmission"));
+    }
+    let mut bitstream = to_bitstream(hex)?;
+    parse_packet(&mut bitstream)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Packet;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<Packet> {
+        parse(input_str)
+    }
+
+    fn part_a(packet: &Packet) -> Result<u64> {
+        Ok(versions_sum(packet))
+    }
+
+    fn part_b(packet: &Packet) -> Result<u64> {
+        Ok(execute(packet))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitstream_reads_across_bytes() {
+        // D2FE28: version 6, type 4, then groups 10111 11110 00101
+        let mut bits = to_bitstream("D2FE28").unwrap();
+        assert_eq!(bits.get(3), 6);
+        assert_eq!(bits.get(3), 4);
+        assert_eq!(bits.get(5), 0b10111);
+        assert_eq!(bits.get(5), 0b11110);
+        assert_eq!(bits.get(5), 0b00101);
+        assert_eq!(bits.consumed(), 21);
+        assert_eq!(bits.get(3), 0);
+        assert!(!bits.exhausted());
+    }
+
+    #[test]
+    fn bitstream_reads_whole_bytes() {
+        let mut bits = to_bitstream("A5F").unwrap();
+        assert_eq!(bits.get(8), 0xA5);
+        assert_eq!(bits.get(4), 0xF);
+        assert_eq!(bits.get(4), 0);
+        assert_eq!(bits.get(1), 0);
+        assert!(bits.exhausted());
+    }
+
+    #[test]
+    fn version_sums() {
+        for (hex, sum) in [("8A004A801A8002F478", 16), ("620080001611562C8802118E34", 12),
+                ("C0015000016115A2E0802F182340", 23), ("A0016C880162017C3686B18A3D4780", 31)] {
+            assert_eq!(Day16::part_a(&Day16::parse(hex).unwrap()).unwrap(), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn evaluation() {
+        for (hex, value) in [("C200B40A82", 3), ("04005AC33890", 54), ("880086C3E88112", 7),
+                ("CE00C43D881120", 9), ("D8005AC2A8F0", 1), ("F600BC2D8F", 0),
+                ("9C005AC2F8F0", 0), ("9C0141080250320F1802104A08", 1)] {
+            assert_eq!(Day16::part_b(&Day16::parse(hex).unwrap()).unwrap(), value, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_transmissions() {
+        let err = Day16::parse("").unwrap_err();
+        assert_eq!(err.to_string(), "day 16, line 1, column 1: Empty transmission");
+        // An operator with a 15-bit length, cut off after 5 of its bits
+        let err = Day16::parse("380").unwrap_err();
+        assert_eq!(err.to_string(),
+            "day 16, line 1, column 1: Operator length runs past the end of the transmission");
+        // EE00D40C823060 with its third subpacket cut off
+        let err = Day16::parse("EE00D40C82").unwrap_err();
+        assert_eq!(err.to_string(),
+            "day 16, line 1, column 11: Packet header runs past the end of the transmission");
+    }
+
+    aoc_common::sample_tests!(Day16, 16, 15);
+}
@@ -0,0 +1,185 @@
+//! Day 17: Trick Shot. Launching probes into a target area.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+pub const PUZZLE: &'static str = include_str!("input17");
+pub const SAMPLE: &'static str = "target area: x=20..30, y=-10..-5";
+
+/// Where a launch ends up relative to the target area
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimResult {
+    /// Hit the target, reaching this height on the way
+    Success(i32),
+    /// Stalled before reaching the target horizontally
+    NotEnoughX,
+    /// Fell short of the target vertically
+    NotEnoughY,
+    /// Passed through the target without stopping in it
+    Overshoot,
+}
+
+/// Launches a probe at velocity (`dx`, `dy`) towards the given target area
+pub fn simulate(dx: i32, dy: i32,
+        x_min: i32, x_max: i32,
+        y_min: i32, y_max: i32) -> SimResult {
+    let t_plummet = dx;
+    let x_plummet = (dx * (dx + 1)) / 2;
+    let y_plummet = if dy < 0 {
+        ((dy - t_plummet + 1)..=dy).sum()
+    }
+    else if dy >= t_plummet {
+        let remainder = dy - t_plummet;
+        (dy * (dy + 1)) / 2 - (remainder * (remainder + 1)) / 2
+    } else {
+        let remainder = t_plummet - dy - 1;
+        (dy * (dy + 1)) / 2 - (remainder * (remainder + 1)) / 2
+    };
+    if x_plummet < x_min {
+        SimResult::NotEnoughX
+    }
+    else if x_plummet >= x_min && x_plummet <= x_max && y_plummet >= y_min {
+        let mut vy = dy - t_plummet;
+        let mut y = y_plummet;
+        let mut ret = y;
+        loop {
+            if y >= y_min && y <= y_max {
+                break SimResult::Success(ret);
+            }
+            if y < y_min {
+                break SimResult::Overshoot;
+            }
+            y += vy;
+            if vy > 0 {
+                ret += vy;
+            }
+            vy -= 1;
+        }
+    } else {
+        // We're off to the right or under
+        let mut t = t_plummet;
+        let mut x = x_plummet;
+        let mut y = y_plummet;
+        let mut vx = 0;
+        let mut vy = dy - t_plummet;
+        loop {
+            if !(t >= 0 && x >= x_min && y <= y_max) {
+                break SimResult::Overshoot;
+            }
+            // Note: Order matter here as we unwind time
+            t -= 1;
+            vx += 1;
+            vy += 1;
+            x -= vx;
+            y -= vy;
+            if x >= x_min && x <= x_max && y >= y_min && y <= y_max {
+                // y(t) starts positive and goes negatave
+                // so the maximum is at the time t where y'(t) stops growing
+                // Or dy < 0 and so whatever
+                let x_t = (0..t).map(|i| dx - i).sum();
+                let y_t = (0..t).map(|i| dy -i).sum();
+                assert_eq!(y, y_t);
+                assert_eq!(x, x_t);
+                let t_max = dy;
+                let y_max = (t_max * (t_max + 1)) / 2;
+                break SimResult::Success(y_max);
+            }
+        }
+    }
+}
+
+/// The highest point of any launch that hits the target
+pub fn simulate_part_a(x_min: i32, x_max: i32, y_min: i32, y_max: i32) -> i32 {
+    let mut ret = 0;
+    // Parameters chosen somewhat arbitrarily
+    'outer: for dx in (0..x_max).rev() {
+        'inner: for dy in (0..x_max).rev() {
+            match simulate(dx, dy, x_min, x_max, y_min, y_max) {
+                SimResult::NotEnoughX => {
+                    break 'outer;
+                },
+                SimResult::NotEnoughY => {
+                    break 'inner;
+                }
+                SimResult::Overshoot => (),
+                SimResult::Success(v) => {
+                    ret = std::cmp::max(ret, v);
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// The number of launch velocities that hit the target
+pub fn part_b(x_min: i32, x_max: i32, y_min: i32, y_max: i32) -> usize{
+    let mut ret = 0;
+    'outer: for dx in (0..=x_max).rev() {
+        'inner: for dy in (y_min..=x_max).rev() {
+            match simulate(dx, dy, x_min, x_max, y_min, y_max) {
+                SimResult::Success(_) => {
+                    log::trace!("Hit with {}, {}", dx, dy);
+                    ret += 1;
+                },
+                SimResult::NotEnoughX => { break 'outer; },
+                SimResult::NotEnoughY => { break 'inner; },
+                SimResult::Overshoot => {},
+            }
+        }
+    }
+    ret
+}
+
+// `line` is the whole input line, for error columns
+fn parse_range(line: &str, range: &str) -> Result<(i32, i32)> {
+    let err = |at: &str, msg: String| Error::parse(Day17::DAY, 1, column(line, at), msg);
+    let (_axis, bounds) = range.trim().split_once('=')
+        .ok_or_else(|| err(range, "No = in range".into()))?;
+    let (lo, hi) = bounds.split_once("..")
+        .ok_or_else(|| err(bounds, "No .. in range".into()))?;
+    let bound = |b: &str| b.parse().map_err(|e| err(b, format!("Bad bound {:?}: {}", b, e)));
+    Ok((bound(lo)?, bound(hi)?))
+}
+
+/// The target area as `(x_min, x_max, y_min, y_max)`
+pub fn parse(input: &str) -> Result<(i32, i32, i32, i32)> {
+    let line = input.trim();
+    let ranges = line.strip_prefix("target area:")
+        .ok_or_else(|| Error::parse(Day17::DAY, 1, 1, "Missing target area prefix"))?;
+    let (x_range, y_range) = ranges.split_once(',')
+        .ok_or_else(|| Error::parse(Day17::DAY, 1, column(line, ranges),
+            "No comma between ranges"))?;
+    let (x_min, x_max) = parse_range(line, x_range)?;
+    let (y_min, y_max) = parse_range(line, y_range)?;
+    Ok((x_min, x_max, y_min, y_max))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    // The target area as (x_min, x_max, y_min, y_max)
+    type Input = (i32, i32, i32, i32);
+    type A = i32;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Self::Input> {
+        parse(input_str)
+    }
+
+    fn part_a(&(x_min, x_max, y_min, y_max): &Self::Input) -> Result<i32> {
+        let soln_a = simulate_part_a(x_min, x_max, y_min, y_max);
+        log::debug!("Debug sample: {:?}", simulate(6, 7, x_min, x_max, y_min, y_max));
+        Ok(soln_a)
+    }
+
+    fn part_b(&(x_min, x_max, y_min, y_max): &Self::Input) -> Result<usize> {
+        Ok(part_b(x_min, x_max, y_min, y_max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::sample_tests!(Day17, 45, 112);
+}
@@ -0,0 +1,520 @@
+//! Day 18: Snailfish. Adding and reducing nested pairs.
+
+use aoc_common::{Answer, Error, Result, Solution};
+use std::fmt;
+use std::sync::Arc;
+use parking_lot::Mutex;
+
+/// How many pairs a pair can be inside of. Deeper pairs can't be exploded,
+/// since exploding needs both halves to be regular numbers.
+pub const MAX_NESTING: u32 = 4;
+
+/// Which side of its parent a node hangs on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    Left, Right, Root,
+}
+
+/// A snailfish number, shared so that leaves can reach their neighbors
+pub type Node = Arc<Mutex<Body>>;
+
+/// Either a regular number (`value`) or a pair of children
+#[derive(Clone, Debug)]
+pub struct Body {
+    pub value: Option<u32>,
+    pub left: Option<Node>,
+    pub right: Option<Node>,
+    pub parent: Option<Node>,
+    pub relation: Relation,
+}
+
+/// A regular number
+pub fn leaf(value: u32) -> Node {
+    Arc::new(Mutex::new(
+        Body { value: Some(value), left: None, right: None,
+                parent: None, relation: Relation::Root }))
+}
+
+/// The pair `[left,right]`, without reducing it
+pub fn append(left: Node, right: Node) -> Node {
+    let ret = Arc::new(Mutex::new(
+        Body { value: None,
+            left: Some(left), right: Some(right), parent: None,
+            relation: Relation::Root }));
+    {
+        let ret_lock = ret.lock();
+        {
+            let mut left = ret_lock.left.as_ref().unwrap().lock();
+            left.parent = Some(ret.clone());
+            left.relation = Relation::Left;
+        }
+        {
+            let mut right = ret_lock.right.as_ref().unwrap().lock();
+            right.parent = Some(ret.clone());
+            right.relation = Relation::Right;
+        }
+    }
+    ret
+}
+
+/// A copy that shares no nodes with the original
+pub fn deep_clone(node: &Node) -> Node {
+    let node = node.lock();
+    if let Some(v) = node.value {
+        leaf(v)
+    } else {
+        let left = deep_clone(node.left.as_ref().unwrap());
+        let right = deep_clone(node.right.as_ref().unwrap());
+        append(left, right)
+    }
+}
+
+fn predecessor(node: &Node) -> Option<Node> {
+    let node_lock = node.lock();
+    match node_lock.left.as_ref() {
+        Some(r) => Some(rightmost_child(r)),
+        None => {
+            if node_lock.relation == Relation::Left {
+                drop(node_lock);
+                find_parent_not_on_right(node)
+            } else {
+                node_lock.parent.as_ref().cloned()
+            }
+        }
+    }
+}
+
+fn find_parent_not_on_right(node: &Node) -> Option<Node> {
+    let node_lock = node.lock();
+    match node_lock.relation {
+        Relation::Root => None,
+        Relation::Left =>
+            find_parent_not_on_right(node_lock.parent.as_ref().unwrap()),
+        Relation::Right =>
+            node_lock.parent.as_ref().cloned(),
+    }
+}
+
+fn rightmost_child(node: &Node) -> Node {
+    match node.lock().right.as_ref() {
+        Some(n) => rightmost_child(n),
+        None => node.clone(),
+    }
+}
+
+fn prev_leaf(node: &Node) -> Option<Node> {
+    predecessor(node).and_then(|pred| {
+        let value = {
+            let lock = pred.lock();
+            lock.value
+        };
+        if value.is_some() {
+            Some(pred)
+        } else {
+            prev_leaf(&pred)
+        }
+    })
+}
+
+fn successor(node: &Node) -> Option<Node> {
+    let node_lock = node.lock();
+    match node_lock.right.as_ref() {
+        Some(r) => Some(leftmost_child(r)),
+        None => {
+            if node_lock.relation == Relation::Right {
+                drop(node_lock);
+                find_parent_not_on_left(node)
+            } else {
+                node_lock.parent.as_ref().cloned()
+            }
+        }
+    }
+}
+
+fn find_parent_not_on_left(node: &Node) -> Option<Node> {
+    let node_lock = node.lock();
+    match node_lock.relation {
+        Relation::Root => None,
+        Relation::Left => node_lock.parent.as_ref().cloned(),
+        Relation::Right =>
+            find_parent_not_on_left(node_lock.parent.as_ref().unwrap())
+    }
+}
+
+fn leftmost_child(node: &Node) -> Node {
+    match node.lock().left.as_ref() {
+        Some(n) => leftmost_child(n),
+        None => node.clone(),
+    }
+}
+
+fn next_leaf(node: &Node) -> Option<Node> {
+    successor(node).and_then(|succ| {
+        let value = {
+            let lock = succ.lock();
+            lock.value
+        };
+        if value.is_some() {
+            Some(succ)
+        } else {
+            next_leaf(&succ)
+        }
+    })
+}
+
+fn replace(to_remove: &Node, replacement: Node) {
+    let (parent, relation) = {
+        let mut replacement_lock = replacement.lock();
+        assert_eq!(replacement_lock.relation, Relation::Root);
+        let (parent, relation) = {
+            let lock = to_remove.lock();
+            let parent = lock.parent.clone();
+            if parent.as_ref().map(|p| p.is_locked()).unwrap_or(false) {
+                panic!("Parent locked on acquisition");
+            }
+            let relation = lock.relation;
+            (parent, relation)
+        };
+        replacement_lock.parent = parent.clone();
+        replacement_lock.relation = relation;
+        (parent, relation)
+    };
+    if let Some(p) = parent.as_ref() {
+        let mut p = p.lock();
+        match relation {
+            Relation::Left => p.left = Some(replacement.clone()),
+            Relation::Right => p.right = Some(replacement.clone()),
+            Relation::Root => panic!("Cant happen, roto with parent"),
+        }
+    }
+}
+
+fn is_regular_pair(node: &Node) -> bool {
+    let node = node.lock();
+    let is_leaf = |child: &Option<Node>| child.as_ref().is_some_and(|c| c.lock().value.is_some());
+    node.value.is_none() && is_leaf(&node.left) && is_leaf(&node.right)
+}
+
+/// Explodes the leftmost pair of two regular numbers nested four deep, if any.
+/// Stops and returns true when a reduction step occurs
+pub fn reduce_explode(node: &Node, depth: u32) -> bool {
+    if depth >= 4 && is_regular_pair(node) {
+        if node.lock().parent.as_ref().map(|p| p.is_locked()).unwrap_or(false) {
+            panic!("Went into explosion with locked parent");
+        }
+        let (left, right) = {
+            let lock = node.lock();
+            let left = lock.left.as_ref().unwrap().clone();
+            let right = lock.right.as_ref().unwrap().clone();
+            (left, right)
+        };
+        let left_val = left.lock().value.expect("Left leaf in explosion");
+        let right_val = right.lock().value.expect("Right leaf in explosion");
+        let pred = prev_leaf(&left);
+        let succ = next_leaf(&right);
+        if let Some(pred) = pred {
+            *pred.lock().value.as_mut().unwrap() += left_val;
+        }
+        if let Some(succ) = succ {
+            *succ.lock().value.as_mut().unwrap() += right_val;
+        }
+        let new_node = leaf(0);
+        replace(node, new_node);
+        return true;
+    }
+
+    let left = {
+        let lock = node.lock();
+        lock.left.as_ref().cloned()
+    };
+    if let Some(l) = left {
+        if reduce_explode(&l, depth + 1) {
+            return true;
+        }
+    }
+
+    let right = {
+        let lock = node.lock();
+        lock.right.as_ref().cloned()
+    };
+    if let Some(r) = right {
+        if reduce_explode(&r, depth + 1) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Splits the leftmost regular number of 10 or more, if any
+pub fn reduce_split(node: &Node) -> bool {
+    let left = {
+        let lock = node.lock();
+        lock.left.as_ref().cloned()
+    };
+    if let Some(l) = left {
+        if reduce_split(&l) {
+            return true;
+        }
+    }
+
+    let value = {
+        let lock = node.lock();
+        lock.value
+    };
+    if let Some(v) = value {
+        if v >= 10 {
+            let left = leaf(v / 2);
+            let right = leaf(v.div_ceil(2));
+            let new_node = append(left, right);
+            replace(node, new_node);
+            return true;
+        }
+    }
+
+    let right = {
+        let lock = node.lock();
+        lock.right.as_ref().cloned()
+    };
+    if let Some(r) = right {
+        if reduce_split(&r) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Performs one reduction step, returning false once fully reduced
+pub fn reduce(node: &Node) -> bool {
+    reduce_explode(node, 0) || reduce_split(node)
+}
+
+/// Snailfish addition: the pair of both, reduced
+pub fn add(left: Node, right: Node) -> Node {
+    let ret = append(left, right);
+    while reduce(&ret) {};
+    ret
+}
+
+fn unexpected(line_no: usize, line: &[u8], idx: usize, expected: &str) -> Error {
+    let found = match line.get(idx) {
+        Some(b) => format!("{:?}", *b as char),
+        None => "end of line".to_owned(),
+    };
+    Error::parse(Day18::DAY, line_no, idx + 1,
+        format!("Expected {}, found {}", expected, found))
+}
+
+// `depth` is how many pairs the node at `idx` is inside of
+fn parse(line_no: usize, line: &[u8], idx: usize, depth: u32) -> Result<(Node, usize)> {
+    match line.get(idx).copied() {
+        Some(byte) if byte >= b'0' && byte <= b'9' => {
+            let node = leaf((byte - b'0') as u32);
+            Ok((node, idx + 1))
+        },
+        Some(b'[') if depth > MAX_NESTING => {
+            Err(Error::parse(Day18::DAY, line_no, idx + 1,
+                format!("Pair is nested more than {} deep", MAX_NESTING)))
+        },
+        Some(b'[') => {
+            let (left, after_left) = parse(line_no, line, idx + 1, depth + 1)?;
+            if line.get(after_left) != Some(&b',') {
+                return Err(unexpected(line_no, line, after_left, "','"));
+            }
+            let (right, after_right) = parse(line_no, line, after_left + 1, depth + 1)?;
+            if line.get(after_right) != Some(&b']') {
+                return Err(unexpected(line_no, line, after_right, "']'"));
+            }
+            Ok((append(left, right), after_right + 1))
+        },
+        _ => Err(unexpected(line_no, line, idx, "a digit or '['")),
+    }
+}
+
+/// Parses a whole line as one snailfish number
+pub fn parse_line(line_no: usize, line: &str) -> Result<Node> {
+    let line = line.trim_end().as_bytes();
+    let (node, end) = parse(line_no, line, 0, 0)?;
+    if end != line.len() {
+        return Err(unexpected(line_no, line, end, "end of line"));
+    }
+    Ok(node)
+}
+
+/// Writes a number back out in its input syntax
+pub fn render(node: &Node) -> String {
+    let node = node.lock();
+    if let Some(v) = node.value {
+        v.to_string()
+    } else {
+        format!("[{},{}]", render(node.left.as_ref().unwrap()),
+            render(node.right.as_ref().unwrap()))
+    }
+}
+
+/// Three times the left magnitude plus twice the right
+pub fn magnitude(node: &Node) -> u32 {
+    let node = node.lock();
+    if let Some(v) = node.value {
+        v
+    } else {
+        3 * magnitude(node.left.as_ref().unwrap())
+            + 2 * magnitude(node.right.as_ref().unwrap())
+    }
+}
+
+/// The sum of all the numbers in order
+pub fn part_a(input: &[Node]) -> Result<Node> {
+    let (first, rest) = input.split_first()
+        .ok_or_else(|| Error::solve(Day18::DAY, "No numbers to add"))?;
+    Ok(rest.iter()
+        .fold(deep_clone(first), |l, r| add(l, deep_clone(r))))
+}
+
+/// The largest magnitude of any sum of two different numbers
+pub fn part_b(input: &[Node]) -> Result<u32> {
+    let len = input.len();
+    (0..len).flat_map(move |i| (0..len).filter_map(move |j| {
+        if i == j { None } else { Some((i, j)) }
+    })).map(|(i, j)| {
+        let left = deep_clone(&input[i]);
+        let right = deep_clone(&input[j]);
+        let addend = add(left, right);
+        magnitude(&addend)
+    }).max().ok_or_else(|| Error::solve(Day18::DAY, "Need at least two numbers to add"))
+}
+
+/// The sum of every number, answered with its magnitude
+pub struct Sum(pub Node);
+
+impl fmt::Display for Sum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", magnitude(&self.0))
+    }
+}
+
+impl Answer for Sum {
+    fn aux(&self) -> Option<String> {
+        Some(render(&self.0))
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<Node>;
+    type A = Sum;
+    type B = u32;
+
+    fn parse(input_str: &str) -> Result<Vec<Node>> {
+        input_str.lines().enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                // Reduced first, so that adding it doesn't nest any pair too deep to explode
+                let node = parse_line(i + 1, l)?;
+                while reduce(&node) {};
+                Ok(node)
+            }).collect()
+    }
+
+    fn part_a(input: &Vec<Node>) -> Result<Sum> {
+        Ok(Sum(part_a(input)?))
+    }
+
+    fn part_b(input: &Vec<Node>) -> Result<u32> {
+        part_b(input)
+    }
+}
+
+pub const PUZZLE: &'static str = include_str!("input18");
+pub const SAMPLE: &'static str =
+"[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUM_SAMPLE: &'static str =
+"[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]";
+
+    fn number(s: &str) -> Node {
+        parse_line(1, s).unwrap()
+    }
+
+    fn explode(s: &str) -> String {
+        let node = number(s);
+        assert!(reduce_explode(&node, 0));
+        render(&node)
+    }
+
+    #[test]
+    fn explodes_leftmost_pair() {
+        assert_eq!(explode("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]");
+        assert_eq!(explode("[7,[6,[5,[4,[3,2]]]]]"), "[7,[6,[5,[7,0]]]]");
+        assert_eq!(explode("[[6,[5,[4,[3,2]]]],1]"), "[[6,[5,[7,0]]],3]");
+        assert_eq!(explode("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"),
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]");
+        assert!(!reduce_explode(&number("[[[[0,9],2],3],4]"), 0));
+    }
+
+    #[test]
+    fn splits_leftmost_large_number() {
+        let node = append(append(leaf(15), leaf(3)), leaf(11));
+        assert!(reduce_split(&node));
+        assert_eq!(render(&node), "[[[7,8],3],11]");
+        assert!(reduce_split(&node));
+        assert_eq!(render(&node), "[[[7,8],3],[5,6]]");
+        assert!(!reduce_split(&node));
+    }
+
+    #[test]
+    fn sample_sum() {
+        let input = Day18::parse(SUM_SAMPLE).unwrap();
+        let sum = part_a(&input).unwrap();
+        assert_eq!(render(&sum), "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]");
+        assert_eq!(magnitude(&sum), 3488);
+    }
+
+    #[test]
+    fn sample_final_sum() {
+        let input = Day18::parse(SAMPLE).unwrap();
+        assert_eq!(render(&part_a(&input).unwrap()),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]");
+    }
+
+    aoc_common::sample_tests!(Day18, 4140, 3993);
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let err = Day18::parse("[1,2]\n[[[[[[1,2],3],4],5],6],7]\n").unwrap_err();
+        assert_eq!(err.to_string(), "day 18, line 2, column 6: Pair is nested more than 4 deep");
+        let input = Day18::parse("[[[[[1,2],3],4],5],6]\n[1,1]\n").unwrap();
+        let sum = part_a(&input).unwrap();
+        assert_eq!(render(&sum), "[[[[0,9],5],6],[1,1]]");
+        assert_eq!(magnitude(&sum), 622);
+    }
+
+    #[test]
+    fn reports_unexpected_byte() {
+        let err = Day18::parse("[1,2]\n[[1,x],2]\n").unwrap_err();
+        assert!(err.to_string().starts_with("day 18, line 2, column 5:"), "{}", err);
+    }
+}
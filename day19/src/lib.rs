@@ -0,0 +1,477 @@
+//! Day 19: Beacon Scanner. Aligning overlapping 3D scans into one map.
+
+use aoc_common::{error::column, Error, Result, Solution};
+use fxhash::{FxHashMap, FxHashSet};
+
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::sync::OnceLock;
+
+/// Scanners overlap when they see at least this many beacons in common
+pub const OVERLAP: usize = 12;
+
+/// A position relative to some scanner
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Point { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    // Squared, so that it stays an integer and is the same in every orientation
+    fn dist2(&self, other: &Point) -> i64 {
+        let d = *self - *other;
+        [d.x, d.y, d.z].iter().map(|&c| (c as i64) * (c as i64)).sum()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, o: Point) -> Point {
+        Point::new(self.x + o.x, self.y + o.y, self.z + o.z)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, o: Point) -> Point {
+        Point::new(self.x - o.x, self.y - o.y, self.z - o.z)
+    }
+}
+
+impl std::str::FromStr for Point {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let coord = |c: &str| c.parse().map_err(|e| Error::parse(Day19::DAY, 1, column(s, c),
+            format!("Bad coordinate {:?}: {}", c, e)));
+        let mut coords = s.split(',');
+        match (coords.next(), coords.next(), coords.next(), coords.next()) {
+            (Some(x), Some(y), Some(z), None) => {
+                Ok(Point::new(coord(x)?, coord(y)?, coord(z)?))
+            },
+            _ => {
+                Err(Error::parse(Day19::DAY, 1, 1, "Expected three coordinates"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// One of the 24 ways a scanner can be turned: which way its x axis
+/// faces, then how far it's rolled around that axis
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Rotation {
+    face: u8,
+    roll: u8,
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation { face: 0, roll: 0 };
+
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        (0..6).flat_map(|face| (0..4).map(move |roll| Rotation { face, roll }))
+    }
+
+    pub fn apply(&self, p: Point) -> Point {
+        let Point { x, y, z } = p;
+        let faced = match self.face {
+            0 => Point::new(x, y, z),
+            1 => Point::new(-x, -y, z),
+            2 => Point::new(y, -x, z),
+            3 => Point::new(-y, x, z),
+            4 => Point::new(z, y, -x),
+            5 => Point::new(-z, y, x),
+            _ => unreachable!(),
+        };
+        (0..self.roll).fold(faced, |p, _| Point::new(p.x, -p.z, p.y))
+    }
+}
+
+/// The beacons one scanner can see, relative to itself
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Scanner {
+    pub beacons: Vec<Point>,
+    // Sorted distances between each pair of beacons, which don't depend
+    // on where the scanner is or which way it faces
+    distances: Vec<i64>,
+}
+
+impl Scanner {
+    pub fn new(beacons: Vec<Point>) -> Self {
+        let mut distances: Vec<i64> = beacons.iter().enumerate()
+            .flat_map(|(i, a)| beacons[i + 1..].iter().map(move |b| a.dist2(b)))
+            .collect();
+        distances.sort_unstable();
+        Scanner { beacons, distances }
+    }
+
+    /// Whether the two scanners could see enough of the same beacons,
+    /// judging by the distances between them. Distances that repeat are
+    /// counted as many times as both scanners see them.
+    pub fn may_overlap(&self, other: &Scanner) -> bool {
+        use std::cmp::Ordering;
+        let needed = OVERLAP * (OVERLAP - 1) / 2;
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < self.distances.len() && j < other.distances.len() {
+            match self.distances[i].cmp(&other.distances[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                },
+            }
+        }
+        shared >= needed
+    }
+}
+
+/// Where a scanner is and how it's turned, relative to scanner 0
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Placement {
+    pub rotation: Rotation,
+    pub position: Point,
+}
+
+impl Placement {
+    pub fn apply(&self, p: Point) -> Point {
+        self.rotation.apply(p) + self.position
+    }
+}
+
+/// Places `scanner` so that at least `OVERLAP` of its beacons land on
+/// the already placed `known` beacons, if it can be
+pub fn align(known: &[Point], scanner: &Scanner) -> Option<Placement> {
+    let mut offsets = FxHashMap::default();
+    for rotation in Rotation::all() {
+        offsets.clear();
+        for b in scanner.beacons.iter().map(|b| rotation.apply(*b)) {
+            for a in known {
+                let count = offsets.entry(*a - b).or_insert(0);
+                *count += 1;
+                if *count >= OVERLAP {
+                    return Some(Placement { rotation, position: *a - b });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Places every scanner relative to scanner 0
+pub fn place_all(scanners: &[Scanner]) -> Result<Vec<Placement>> {
+    place(scanners).map_err(unplaced)
+}
+
+fn unplaced(i: usize) -> Error {
+    Error::solve(Day19::DAY, format!("Scanner {} doesn't overlap with any other", i))
+}
+
+// Fails with the first scanner that couldn't be placed
+fn place(scanners: &[Scanner]) -> std::result::Result<Vec<Placement>, usize> {
+    let origin = Placement { rotation: Rotation::IDENTITY, position: Point::default() };
+    let mut placements = vec![None; scanners.len()];
+    placements[0] = Some(origin);
+    let mut frontier = vec![0];
+    while let Some(i) = frontier.pop() {
+        let placement = placements[i].expect("Placed before being explored");
+        let known: Vec<Point> = scanners[i].beacons.iter().map(|b| placement.apply(*b)).collect();
+        for j in 0..scanners.len() {
+            if placements[j].is_some() || !scanners[i].may_overlap(&scanners[j]) {
+                continue;
+            }
+            if let Some(p) = align(&known, &scanners[j]) {
+                log::debug!("Scanner {} is at {} relative to scanner 0", j, p.position);
+                placements[j] = Some(p);
+                frontier.push(j);
+            }
+        }
+    }
+    placements.into_iter().enumerate().map(|(i, p)| p.ok_or(i)).collect()
+}
+
+/// The scanner reports, along with where the scanners are, which is worked
+/// out the first time a part asks and then shared with the other
+#[derive(Debug)]
+pub struct Scanners {
+    pub scanners: Vec<Scanner>,
+    placements: OnceLock<std::result::Result<Vec<Placement>, usize>>,
+}
+
+impl Scanners {
+    pub fn new(scanners: Vec<Scanner>) -> Self {
+        Scanners { scanners, placements: OnceLock::new() }
+    }
+
+    /// Every scanner placed relative to scanner 0, as by `place_all`
+    pub fn placements(&self) -> Result<&[Placement]> {
+        self.placements.get_or_init(|| place(&self.scanners))
+            .as_deref().map_err(|&i| unplaced(i))
+    }
+}
+
+/// Every beacon, relative to scanner 0
+pub fn beacons(scanners: &[Scanner], placements: &[Placement]) -> FxHashSet<Point> {
+    scanners.iter().zip(placements)
+        .flat_map(|(s, p)| s.beacons.iter().map(move |b| p.apply(*b)))
+        .collect()
+}
+
+/// The largest Manhattan distance between any two scanners
+pub fn max_distance(placements: &[Placement]) -> i32 {
+    placements.iter()
+        .flat_map(|a| placements.iter().map(move |b| a.position.manhattan(&b.position)))
+        .max().unwrap_or(0)
+}
+
+/// The scanner reports, each a `--- scanner N ---` header and then
+/// one beacon per line
+pub fn parse(input: &str) -> Result<Vec<Scanner>> {
+    let mut ret = vec![];
+    let mut beacons = None;
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with("---") {
+            if line != format!("--- scanner {} ---", ret.len() + beacons.is_some() as usize) {
+                return Err(Error::parse(Day19::DAY, i + 1, 1,
+                    format!("Unexpected scanner header {:?}", line)));
+            }
+            ret.extend(beacons.replace(vec![]).map(Scanner::new));
+            continue;
+        }
+        let point = line.parse().map_err(|e: Error| e.offset_lines(i))?;
+        beacons.as_mut()
+            .ok_or_else(|| Error::parse(Day19::DAY, i + 1, 1, "Beacon before any scanner header"))?
+            .push(point);
+    }
+    ret.extend(beacons.map(Scanner::new));
+    if ret.is_empty() {
+        return Err(Error::parse(Day19::DAY, 1, 1, "No scanners"));
+    }
+    Ok(ret)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = Scanners;
+    type A = usize;
+    type B = i32;
+
+    fn parse(input_str: &str) -> Result<Scanners> {
+        parse(input_str).map(Scanners::new)
+    }
+
+    fn part_a(input: &Scanners) -> Result<usize> {
+        Ok(beacons(&input.scanners, input.placements()?).len())
+    }
+
+    fn part_b(input: &Scanners) -> Result<i32> {
+        Ok(max_distance(input.placements()?))
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+pub const SAMPLE: &'static str =
+"--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations_are_distinct() {
+        let p = Point::new(1, 2, 3);
+        let turned: FxHashSet<Point> = Rotation::all().map(|r| r.apply(p)).collect();
+        assert_eq!(turned.len(), 24);
+        assert!(turned.iter().all(|q| q.dist2(&Point::default()) == 14));
+    }
+
+    #[test]
+    fn places_sample_scanners() {
+        let scanners = parse(SAMPLE).unwrap();
+        let placements = place_all(&scanners).unwrap();
+        assert_eq!(placements[1].position, Point::new(68, -1246, -43));
+        assert_eq!(placements[2].position, Point::new(1105, -1205, 1229));
+        assert_eq!(placements[3].position, Point::new(-92, -2380, -20));
+        assert_eq!(placements[4].position, Point::new(-20, -1133, 1061));
+    }
+
+    #[test]
+    fn overlap_counts_repeated_distances() {
+        // Twelve beacons on a line, one apart, so most distances repeat
+        let line = |x0: i32| Scanner::new((0..12).map(|x| Point::new(x0 + x, 0, 0)).collect());
+        assert!(line(0).may_overlap(&line(500)));
+        let short = Scanner::new((0..11).map(|x| Point::new(x, 0, 0)).collect());
+        assert!(!short.may_overlap(&line(0)));
+    }
+
+    #[test]
+    fn places_scanners_when_solving() {
+        let input = Day19::parse("--- scanner 0 ---\n1,2,3\n--- scanner 1 ---\n4,5,6\n").unwrap();
+        let err = Day19::part_a(&input).unwrap_err();
+        assert_eq!(err.to_string(), "day 19: Scanner 1 doesn't overlap with any other");
+    }
+
+    aoc_common::sample_tests!(Day19, 79, 3621);
+
+    #[test]
+    fn reports_bad_coordinates() {
+        let err = Day19::parse("--- scanner 0 ---\n1,2,3\n4,x,6\n").unwrap_err();
+        assert_eq!(err.to_string(), "day 19, line 3, column 3: Bad coordinate \"x\": invalid digit found in string");
+    }
+}
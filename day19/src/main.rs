@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day19::Day19>(day19::PUZZLE, day19::SAMPLE)
+}
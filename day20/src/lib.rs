@@ -0,0 +1,218 @@
+//! Day 20: Trench Map. Enhancing an image on an infinite background.
+
+use aoc_common::{Answer, Error, Grid, Result, Solution};
+
+use std::fmt;
+
+/// One output pixel for each 3x3 neighborhood, read as 9 bits
+pub const ALGORITHM_LEN: usize = 512;
+pub const STEPS_A: usize = 2;
+pub const STEPS_B: usize = 50;
+
+/// A finite image, surrounded on all sides by pixels of the background color
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub pixels: Grid<bool>,
+    pub background: bool,
+}
+
+impl Image {
+    // Positions outside the stored pixels are background
+    fn pixel(&self, i: isize, j: isize) -> bool {
+        if i < 0 || j < 0 {
+            return self.background;
+        }
+        self.pixels.get((i as usize, j as usize)).copied().unwrap_or(self.background)
+    }
+
+    /// Applies the enhancement algorithm once. The image grows by a pixel
+    /// on each side, since that's as far as the old image can reach.
+    pub fn enhance(&self, algorithm: &[bool]) -> Image {
+        let rows = self.pixels.rows() + 2;
+        let cols = self.pixels.cols() + 2;
+        // Output (i, j) is centered on input (i - 1, j - 1)
+        let pixels = Grid::from_fn(rows, cols, |(i, j)| {
+            let mut idx = 0;
+            for di in -2..=0 {
+                for dj in -2..=0 {
+                    let lit = self.pixel(i as isize + di, j as isize + dj);
+                    idx = (idx << 1) | (lit as usize);
+                }
+            }
+            algorithm[idx]
+        });
+        let background = algorithm[if self.background { ALGORITHM_LEN - 1 } else { 0 }];
+        Image { pixels, background }
+    }
+
+    /// How many pixels are lit, if that's a finite number
+    pub fn lit(&self) -> Option<usize> {
+        if self.background {
+            None
+        } else {
+            Some(self.pixels.iter().filter(|(_, &p)| p).count())
+        }
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pixels.render())
+    }
+}
+
+/// The image after each of `steps` enhancements, starting with the original
+pub fn enhance_steps(image: &Image, algorithm: &[bool], steps: usize) -> Vec<Image> {
+    let mut ret = Vec::with_capacity(steps + 1);
+    ret.push(image.clone());
+    for step in 1..=steps {
+        let next = ret[step - 1].enhance(algorithm);
+        log::debug!("After step {}:\n{}", step, next);
+        ret.push(next);
+    }
+    ret
+}
+
+/// The image after `steps` enhancements, keeping only the latest one
+pub fn enhance_n(image: &Image, algorithm: &[bool], steps: usize) -> Image {
+    (1..=steps).fold(image.clone(), |image, step| {
+        let next = image.enhance(algorithm);
+        log::debug!("After step {}:\n{}", step, next);
+        next
+    })
+}
+
+fn lit_after(image: &Image, steps: usize) -> Result<usize> {
+    image.lit().ok_or_else(|| Error::solve(Day20::DAY,
+        format!("Infinitely many pixels are lit after {} steps", steps)))
+}
+
+/// Lit pixels after `steps` enhancements, with every image along the way
+pub fn solve(image: &Image, algorithm: &[bool], steps: usize) -> Result<Enhanced> {
+    let images = enhance_steps(image, algorithm, steps);
+    let count = lit_after(images.last().expect("Includes the original"), steps)?;
+    Ok(Enhanced { count, images })
+}
+
+/// Lit pixels after `steps` enhancements, without keeping the images
+pub fn count_lit(image: &Image, algorithm: &[bool], steps: usize) -> Result<usize> {
+    lit_after(&enhance_n(image, algorithm, steps), steps)
+}
+
+/// The number of lit pixels, shown with the images that led to it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Enhanced {
+    pub count: usize,
+    pub images: Vec<Image>,
+}
+
+impl fmt::Display for Enhanced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+impl Answer for Enhanced {
+    fn aux(&self) -> Option<String> {
+        let images: Vec<String> = self.images.iter().map(Image::to_string).collect();
+        Some(images.join("\n\n"))
+    }
+}
+
+/// The enhancement algorithm, which may be wrapped over several lines,
+/// then a blank line and the image
+pub fn parse(input: &str) -> Result<(Vec<bool>, Image)> {
+    let mut algorithm = Vec::with_capacity(ALGORITHM_LEN);
+    let mut lines = input.lines().enumerate();
+    for (i, line) in lines.by_ref() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        for (j, b) in line.bytes().enumerate() {
+            match b {
+                b'#' => algorithm.push(true),
+                b'.' => algorithm.push(false),
+                _ => return Err(Error::parse(Day20::DAY, i + 1, j + 1,
+                    format!("{:?} is not a pixel", b as char))),
+            }
+        }
+    }
+    if algorithm.len() != ALGORITHM_LEN {
+        return Err(Error::parse(Day20::DAY, 1, 1, format!(
+            "Enhancement algorithm has {} entries, expected {}", algorithm.len(), ALGORITHM_LEN)));
+    }
+    let image_start = match lines.next() {
+        Some((i, _)) => i,
+        None => return Err(Error::parse(Day20::DAY, 1, 1, "No image after the algorithm")),
+    };
+    let image_str = input.lines().skip(image_start).collect::<Vec<_>>().join("\n");
+    let pixels = Grid::parse_pixels(Day20::DAY, &image_str)
+        .map_err(|e| e.offset_lines(image_start))?;
+    Ok((algorithm, Image { pixels, background: false }))
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = (Vec<bool>, Image);
+    type A = Enhanced;
+    type B = usize;
+
+    fn parse(input_str: &str) -> Result<Self::Input> {
+        parse(input_str)
+    }
+
+    fn part_a((algorithm, image): &Self::Input) -> Result<Enhanced> {
+        solve(image, algorithm, STEPS_A)
+    }
+
+    fn part_b((algorithm, image): &Self::Input) -> Result<usize> {
+        count_lit(image, algorithm, STEPS_B)
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+pub const SAMPLE: &'static str =
+"..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..##\
+#..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###\
+.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#.\
+.#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#.....\
+.#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#..\
+...####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.....\
+..##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enhances_sample_twice() {
+        let (algorithm, image) = parse(SAMPLE).unwrap();
+        let images = enhance_steps(&image, &algorithm, 2);
+        assert_eq!(images[1].pixels.render(),
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.");
+        assert_eq!(images[2].lit(), Some(35));
+        assert_eq!(enhance_n(&image, &algorithm, 2), images[2]);
+    }
+
+    #[test]
+    fn background_flips() {
+        let mut algorithm = vec![false; ALGORITHM_LEN];
+        algorithm[0] = true;
+        let image = Image { pixels: Grid::parse_pixels(20, ".").unwrap(), background: false };
+        let once = image.enhance(&algorithm);
+        assert!(once.background);
+        assert_eq!(once.lit(), None);
+        assert!(!once.enhance(&algorithm).background);
+    }
+
+    aoc_common::sample_tests!(Day20, 35, 3351);
+}
@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day20::Day20>(day20::PUZZLE, day20::SAMPLE)
+}
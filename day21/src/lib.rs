@@ -0,0 +1,158 @@
+//! Day 21: Dirac Dice. A board game, played once and then in every universe at once.
+
+use aoc_common::{error::column, Error, Result, Solution};
+use fxhash::FxHashMap;
+
+pub const BOARD_SIZE: u32 = 10;
+pub const TARGET_A: u32 = 1000;
+pub const TARGET_B: u32 = 21;
+
+/// How many ways three rolls of the Dirac die add up to 3, 4, ..., 9
+pub const DIRAC_SUMS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// Moves `roll` spaces around the board, whose spaces are numbered 1 to 10
+pub fn advance(pos: u32, roll: u32) -> u32 {
+    (pos - 1 + roll) % BOARD_SIZE + 1
+}
+
+/// Plays with the deterministic die. Returns the losing score times
+/// the number of rolls.
+pub fn part_a(start: [u32; 2]) -> u64 {
+    let mut pos = start;
+    let mut score = [0, 0];
+    let mut rolls = 0;
+    let mut turn = 0;
+    loop {
+        // The die rolls 1 to 100 in order, then wraps around
+        let roll: u32 = (0..3).map(|i| (rolls + i) % 100 + 1).sum();
+        rolls += 3;
+        pos[turn] = advance(pos[turn], roll);
+        score[turn] += pos[turn];
+        if score[turn] >= TARGET_A {
+            return score[1 - turn] as u64 * rolls as u64;
+        }
+        turn = 1 - turn;
+    }
+}
+
+/// Everything about a game that affects how it can go on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct State {
+    pub pos: [u32; 2],
+    pub score: [u32; 2],
+    pub turn: usize,
+}
+
+/// How many universes are in each state
+pub type Universes = FxHashMap<State, u64>;
+
+/// One turn in every universe. Games that end are removed, and the
+/// universes they ended in are added to `wins`.
+pub fn step(prev: Universes, wins: &mut [u64; 2]) -> Universes {
+    let mut ret = Universes::with_capacity_and_hasher(prev.len(),
+        fxhash::FxBuildHasher::default());
+    for (state, count) in prev {
+        let turn = state.turn;
+        for (roll, ways) in DIRAC_SUMS {
+            let mut next = state;
+            next.pos[turn] = advance(state.pos[turn], roll);
+            next.score[turn] += next.pos[turn];
+            if next.score[turn] >= TARGET_B {
+                wins[turn] += count * ways;
+            } else {
+                next.turn = 1 - turn;
+                *ret.entry(next).or_insert(0) += count * ways;
+            }
+        }
+    }
+    ret
+}
+
+/// The number of universes each player wins in with the Dirac die
+pub fn dirac_wins(start: [u32; 2]) -> [u64; 2] {
+    let mut universes = Universes::default();
+    universes.insert(State { pos: start, score: [0, 0], turn: 0 }, 1);
+    let mut wins = [0, 0];
+    while !universes.is_empty() {
+        universes = step(universes, &mut wins);
+    }
+    wins
+}
+
+/// The universes won by whoever wins in more of them
+pub fn part_b(start: [u32; 2]) -> u64 {
+    let [a, b] = dirac_wins(start);
+    std::cmp::max(a, b)
+}
+
+/// The two starting positions, from `Player N starting position: P` lines
+pub fn parse(input: &str) -> Result<[u32; 2]> {
+    let mut ret = [0; 2];
+    let mut lines = input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+    for (player, slot) in ret.iter_mut().enumerate() {
+        let (i, line) = lines.next().ok_or_else(|| Error::parse(Day21::DAY, 1, 1,
+            format!("Missing player {}'s starting position", player + 1)))?;
+        let line = line.trim_end();
+        let expected = format!("Player {} starting position: ", player + 1);
+        let pos = line.strip_prefix(&expected).ok_or_else(|| Error::parse(Day21::DAY, i + 1, 1,
+            format!("Expected {:?}, got {:?}", expected.trim_end(), line)))?;
+        *slot = pos.parse().map_err(|e| Error::parse(Day21::DAY, i + 1, column(line, pos),
+            format!("Bad position {:?}: {}", pos, e)))?;
+        if *slot < 1 || *slot > BOARD_SIZE {
+            return Err(Error::parse(Day21::DAY, i + 1, column(line, pos),
+                format!("Position {} is off the board", slot)));
+        }
+    }
+    Ok(ret)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = [u32; 2];
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<[u32; 2]> {
+        parse(input_str)
+    }
+
+    fn part_a(start: &[u32; 2]) -> Result<u64> {
+        Ok(part_a(*start))
+    }
+
+    fn part_b(start: &[u32; 2]) -> Result<u64> {
+        Ok(part_b(*start))
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+pub const SAMPLE: &'static str =
+"Player 1 starting position: 4
+Player 2 starting position: 8";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_wraps_to_one() {
+        assert_eq!(advance(7, 5), 2);
+        assert_eq!(advance(4, 6), 10);
+        assert_eq!(advance(10, 100), 10);
+    }
+
+    #[test]
+    fn dirac_wins_per_player() {
+        assert_eq!(dirac_wins([4, 8]), [444356092776315, 341960390180808]);
+    }
+
+    aoc_common::sample_tests!(Day21, 739785, 444356092776315u64);
+
+    #[test]
+    fn rejects_positions_off_the_board() {
+        let err = Day21::parse("Player 1 starting position: 4\nPlayer 2 starting position: 11").unwrap_err();
+        assert_eq!(err.to_string(), "day 21, line 2, column 29: Position 11 is off the board");
+    }
+}
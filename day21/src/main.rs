@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day21::Day21>(day21::PUZZLE, day21::SAMPLE)
+}
@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day22::Day22>(day22::PUZZLE, day22::SAMPLE)
+}
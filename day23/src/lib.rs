@@ -0,0 +1,245 @@
+//! Day 23: Amphipod. Sorting amphipods into their rooms for the least energy.
+
+use aoc_common::graph::dijkstra_by;
+use aoc_common::{Error, Result, Solution};
+
+use std::fmt;
+
+pub const HALL_LEN: usize = 11;
+pub const ROOMS: usize = 4;
+pub const MAX_DEPTH: usize = 4;
+/// A space with no amphipod in it. Amphipods are 0 to 3 for A to D.
+pub const EMPTY: u8 = 4;
+/// Energy per step for each kind of amphipod
+pub const ENERGY: [u64; ROOMS] = [1, 10, 100, 1000];
+/// The rows part b unfolds between the two rows of the diagram
+pub const FOLDED: [[u8; ROOMS]; 2] = [[3, 2, 1, 0], [3, 1, 0, 2]];
+
+/// The hallway space just outside `room`, which nobody may stop on
+pub fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn is_entrance(hall: usize) -> bool {
+    (0..ROOMS).any(|room| entrance(room) == hall)
+}
+
+/// Where every amphipod is. Rooms are listed from the top down, and
+/// only the first `depth` spaces of each exist.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Burrow {
+    pub hall: [u8; HALL_LEN],
+    pub rooms: [[u8; MAX_DEPTH]; ROOMS],
+    pub depth: usize,
+}
+
+impl Burrow {
+    /// Whether `room` holds only the amphipods that belong there
+    fn room_is_clean(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth].iter().all(|&a| a == EMPTY || a as usize == room)
+    }
+
+    pub fn is_organized(&self) -> bool {
+        (0..ROOMS).all(|r| self.rooms[r][..self.depth].iter().all(|&a| a as usize == r))
+    }
+
+    // Whether the hallway between `from` (excluded) and `to` (included) is empty
+    fn hall_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to { from + 1..=to } else { to..=from - 1 };
+        self.hall[range].iter().all(|&a| a == EMPTY)
+    }
+
+    /// Amphipods in the hallway that can go straight to the bottom of their room
+    fn moves_home(&self, out: &mut Vec<(Burrow, u64)>) {
+        for h in 0..HALL_LEN {
+            let kind = self.hall[h];
+            if kind == EMPTY {
+                continue;
+            }
+            let room = kind as usize;
+            let door = entrance(room);
+            if !self.room_is_clean(room) || !self.hall_clear(h, door) {
+                continue;
+            }
+            let depth = self.rooms[room][..self.depth].iter()
+                .rposition(|&a| a == EMPTY).expect("Clean rooms have space for the rest");
+            let mut next = *self;
+            next.hall[h] = EMPTY;
+            next.rooms[room][depth] = kind;
+            let steps = h.abs_diff(door) + depth + 1;
+            out.push((next, steps as u64 * ENERGY[room]));
+        }
+    }
+
+    /// The top amphipod of each room that has strangers in it moving
+    /// to every hallway space it can reach
+    fn moves_out(&self, out: &mut Vec<(Burrow, u64)>) {
+        for room in 0..ROOMS {
+            if self.room_is_clean(room) {
+                continue;
+            }
+            let depth = match self.rooms[room][..self.depth].iter().position(|&a| a != EMPTY) {
+                Some(d) => d,
+                None => continue,
+            };
+            let kind = self.rooms[room][depth];
+            let door = entrance(room);
+            let free = |h: &usize| self.hall[*h] == EMPTY;
+            let lefts = (0..door).rev().take_while(free);
+            let rights = (door + 1..HALL_LEN).take_while(free);
+            for h in lefts.chain(rights).filter(|h| !is_entrance(*h)) {
+                let mut next = *self;
+                next.rooms[room][depth] = EMPTY;
+                next.hall[h] = kind;
+                let steps = depth + 1 + h.abs_diff(door);
+                out.push((next, steps as u64 * ENERGY[kind as usize]));
+            }
+        }
+    }
+
+    /// Every legal move from here and its cost. Going home is never a
+    /// mistake, so when someone can, that's the only move considered.
+    pub fn moves(&self, out: &mut Vec<(Burrow, u64)>) {
+        self.moves_home(out);
+        if out.is_empty() {
+            self.moves_out(out);
+        }
+    }
+
+    /// The part b burrow, with the two extra rows inserted in the middle
+    pub fn unfold(&self) -> Burrow {
+        let mut ret = *self;
+        ret.depth = 4;
+        for (room, cells) in ret.rooms.iter_mut().enumerate() {
+            *cells = [self.rooms[room][0], FOLDED[0][room],
+                FOLDED[1][room], self.rooms[room][1]];
+        }
+        ret
+    }
+}
+
+fn cell(a: u8) -> char {
+    if a == EMPTY { '.' } else { (b'A' + a) as char }
+}
+
+/// Draws the burrow the way the puzzle does
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "#############")?;
+        let hall: String = self.hall.iter().map(|&a| cell(a)).collect();
+        writeln!(f, "#{}#", hall)?;
+        for d in 0..self.depth {
+            let row: Vec<String> = (0..ROOMS).map(|r| cell(self.rooms[r][d]).to_string()).collect();
+            let edge = if d == 0 { "##" } else { "  " };
+            writeln!(f, "{}#{}#{}", edge, row.join("#"), edge.trim())?;
+        }
+        write!(f, "  #########")
+    }
+}
+
+/// The least energy that gets every amphipod home
+pub fn organize(burrow: &Burrow) -> Result<u64> {
+    dijkstra_by(*burrow, |b, out| b.moves(out), |b| b.is_organized())
+        .map(|(cost, _)| cost)
+        .ok_or_else(|| Error::solve(Day23::DAY, "The amphipods can't be organized"))
+}
+
+/// The diagram, with an empty hallway and two rows of rooms
+pub fn parse(input: &str) -> Result<Burrow> {
+    let mut burrow = Burrow { hall: [EMPTY; HALL_LEN], rooms: [[EMPTY; MAX_DEPTH]; ROOMS], depth: 0 };
+    let mut counts = [0; ROOMS];
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if i == 1 && line != "#...........#" {
+            return Err(Error::parse(Day23::DAY, i + 1, 1,
+                format!("Expected an empty hallway, got {:?}", line)));
+        }
+        let row: Vec<(usize, u8)> = line.bytes().enumerate()
+            .filter(|&(_, b)| b != b'#' && b != b' ' && b != b'.').collect();
+        if i < 2 || row.is_empty() {
+            continue;
+        }
+        if row.len() != ROOMS || burrow.depth == 2 {
+            return Err(Error::parse(Day23::DAY, i + 1, 1,
+                "Expected two rows of four amphipods"));
+        }
+        for (room, (j, b)) in row.into_iter().enumerate() {
+            if b < b'A' || b > b'D' || j != 3 + 2 * room {
+                return Err(Error::parse(Day23::DAY, i + 1, j + 1,
+                    format!("Unexpected {:?} in room {}", b as char, room)));
+            }
+            burrow.rooms[room][burrow.depth] = b - b'A';
+            counts[(b - b'A') as usize] += 1;
+        }
+        burrow.depth += 1;
+    }
+    if burrow.depth != 2 {
+        return Err(Error::parse(Day23::DAY, 1, 1, "Expected two rows of four amphipods"));
+    }
+    if counts != [2; ROOMS] {
+        return Err(Error::parse(Day23::DAY, 3, 1, "Expected two amphipods of each kind"));
+    }
+    Ok(burrow)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Input = Burrow;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<Burrow> {
+        parse(input_str)
+    }
+
+    fn part_a(burrow: &Burrow) -> Result<u64> {
+        organize(burrow)
+    }
+
+    fn part_b(burrow: &Burrow) -> Result<u64> {
+        let unfolded = burrow.unfold();
+        log::debug!("Unfolded burrow:\n{}", unfolded);
+        organize(&unfolded)
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+pub const SAMPLE: &'static str =
+"#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_the_diagram() {
+        let burrow = parse(SAMPLE).unwrap();
+        assert_eq!(burrow.to_string(), SAMPLE);
+        assert_eq!(burrow.unfold().to_string(),
+"#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########");
+    }
+
+    #[test]
+    fn cannot_stop_outside_rooms() {
+        let burrow = parse(SAMPLE).unwrap();
+        let mut moves = vec![];
+        burrow.moves(&mut moves);
+        // Four rooms, each with seven hallway spaces to stop on
+        assert_eq!(moves.len(), 28);
+        assert!(moves.iter().all(|(b, _)| (0..ROOMS).all(|r| b.hall[entrance(r)] == EMPTY)));
+    }
+
+    aoc_common::sample_tests!(Day23, 12521, 44169);
+}
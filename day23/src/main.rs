@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day23::Day23>(day23::PUZZLE, day23::SAMPLE)
+}
@@ -0,0 +1,399 @@
+//! Day 24: Arithmetic Logic Unit. Finding the model numbers MONAD accepts.
+
+use aoc_common::{error::column, Error, Result, Solution};
+
+use std::fmt;
+
+pub const REGISTERS: [char; 4] = ['w', 'x', 'y', 'z'];
+pub const Z: usize = 3;
+/// The most digits a model number can have and still fit in a u64
+pub const MAX_DIGITS: usize = 19;
+/// Each digit is checked by a copy of this block, differing only in
+/// the constants marked `_`
+pub const BLOCK: [&'static str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _",
+    "eql x w", "eql x 0", "mul y 0", "add y 25", "mul y x", "add y 1",
+    "mul z y", "mul y 0", "add y w", "add y _", "mul y x", "add z y",
+];
+
+/// A register (by index into `REGISTERS`) or a literal number
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Reg(usize),
+    Num(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+/// Reading a digit into a register, or an operation whose result goes
+/// into its first operand
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instr {
+    Inp(usize),
+    Binary { typ: Opcode, a: usize, b: Operand },
+}
+
+/// The instructions, each with the line it came from
+pub type Program = Vec<(usize, Instr)>;
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Reg(r) => write!(f, "{}", REGISTERS[*r]),
+            Operand::Num(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instr::Inp(r) => write!(f, "inp {}", REGISTERS[*r]),
+            Instr::Binary { typ, a, b } => {
+                let name = match typ {
+                    Opcode::Add => "add",
+                    Opcode::Mul => "mul",
+                    Opcode::Div => "div",
+                    Opcode::Mod => "mod",
+                    Opcode::Eql => "eql",
+                };
+                write!(f, "{} {} {}", name, REGISTERS[*a], b)
+            }
+        }
+    }
+}
+
+/// The result of one operation, or None if it would crash the ALU
+pub fn eval(typ: Opcode, a: i64, b: i64) -> Option<i64> {
+    match typ {
+        Opcode::Add => a.checked_add(b),
+        Opcode::Mul => a.checked_mul(b),
+        Opcode::Div => if b == 0 { None } else { Some(a / b) },
+        Opcode::Mod => if a < 0 || b <= 0 { None } else { Some(a % b) },
+        Opcode::Eql => Some(if a == b { 1 } else { 0 }),
+    }
+}
+
+/// The registers w, x, y and z after running the program on `input`,
+/// starting from all zeros
+pub fn execute(program: &[(usize, Instr)], input: &[i64]) -> Result<[i64; 4]> {
+    let mut regs = [0; 4];
+    let mut input = input.iter();
+    for &(line, instr) in program {
+        match instr {
+            Instr::Inp(r) => {
+                regs[r] = *input.next().ok_or_else(|| Error::solve_at(Day24::DAY, line,
+                    "Ran out of input"))?;
+            },
+            Instr::Binary { typ, a, b } => {
+                let b = match b {
+                    Operand::Reg(r) => regs[r],
+                    Operand::Num(n) => n,
+                };
+                regs[a] = eval(typ, regs[a], b).ok_or_else(|| Error::solve_at(Day24::DAY, line,
+                    format!("{} crashes the ALU with {} = {}", instr, REGISTERS[a], regs[a])))?;
+            },
+        }
+    }
+    Ok(regs)
+}
+
+/// The constants that make one digit's block different from the others
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block {
+    pub div: i64,
+    pub check: i64,
+    pub offset: i64,
+}
+
+/// Splits MONAD into its per-digit blocks, checking they follow `BLOCK`
+pub fn blocks(program: &[(usize, Instr)]) -> Result<Vec<Block>> {
+    if !program.len().is_multiple_of(BLOCK.len()) {
+        return Err(Error::solve(Day24::DAY, format!(
+            "{} instructions isn't a whole number of {}-instruction blocks", program.len(), BLOCK.len())));
+    }
+    let digits = program.len() / BLOCK.len();
+    if digits == 0 {
+        return Err(Error::solve(Day24::DAY, "MONAD has no instructions"));
+    }
+    if digits > MAX_DIGITS {
+        return Err(Error::solve(Day24::DAY, format!(
+            "Model numbers of {} digits don't fit the answer, the most is {}", digits, MAX_DIGITS)));
+    }
+    program.chunks(BLOCK.len()).map(|block| {
+        let mut constants = Vec::with_capacity(3);
+        for ((line, instr), expected) in block.iter().zip(BLOCK) {
+            let actual = instr.to_string();
+            let matches = match expected.strip_suffix('_') {
+                Some(prefix) => match instr {
+                    Instr::Binary { b: Operand::Num(n), .. } if actual.starts_with(prefix) => {
+                        constants.push(*n);
+                        true
+                    },
+                    _ => false,
+                },
+                None => actual == expected,
+            };
+            if !matches {
+                return Err(Error::solve_at(Day24::DAY, *line,
+                    format!("Expected {:?}, got {:?}", expected, actual)));
+            }
+        }
+        Ok(Block { div: constants[0], check: constants[1], offset: constants[2] })
+    }).collect()
+}
+
+/// The largest or smallest model number MONAD accepts.
+/// z is a stack of base-26 digits. A block with `div z 1` and a check
+/// of 10 or more always pushes its digit plus its offset. A block with
+/// `div z 26` pops that back off, and only avoids pushing its own
+/// digit when that digit is the popped one plus its check. So each
+/// push block is paired with a pop block, and the two digits must
+/// differ by the push's offset plus the pop's check.
+pub fn model_number(blocks: &[Block], largest: bool) -> Result<Vec<i64>> {
+    let mut digits = vec![0; blocks.len()];
+    let mut stack = Vec::new();
+    for (j, block) in blocks.iter().enumerate() {
+        match block.div {
+            1 if block.check >= 10 => stack.push(j),
+            26 => {
+                let i = stack.pop().ok_or_else(|| Error::solve(Day24::DAY,
+                    format!("Block {} pops from an empty stack", j + 1)))?;
+                let delta = blocks[i].offset + block.check;
+                if delta.abs() > 8 {
+                    return Err(Error::solve(Day24::DAY, format!(
+                        "Digits {} and {} can't differ by {}", i + 1, j + 1, delta)));
+                }
+                digits[i] = if largest { 9.min(9 - delta) } else { 1.max(1 - delta) };
+                digits[j] = digits[i] + delta;
+            },
+            _ => return Err(Error::solve(Day24::DAY, format!(
+                "Block {} neither pushes nor pops (div {}, check {})", j + 1, block.div, block.check))),
+        }
+    }
+    if !stack.is_empty() {
+        return Err(Error::solve(Day24::DAY, format!("{} blocks are never popped", stack.len())));
+    }
+    Ok(digits)
+}
+
+/// Finds the model number, then runs MONAD on it to make sure it's valid
+pub fn solve(program: &[(usize, Instr)], largest: bool) -> Result<u64> {
+    let digits = model_number(&blocks(program)?, largest)?;
+    let regs = execute(program, &digits)?;
+    log::debug!("Registers after running on {:?}: {:?}", digits, regs);
+    if regs[Z] != 0 {
+        return Err(Error::solve(Day24::DAY, format!("MONAD rejects {:?} with z = {}", digits, regs[Z])));
+    }
+    Ok(digits.iter().fold(0, |acc, &d| acc * 10 + d as u64))
+}
+
+// `line` is the whole input line, for error columns
+fn parse_register(line: &str, token: &str) -> Result<usize> {
+    REGISTERS.iter().position(|r| token.len() == 1 && token.starts_with(*r))
+        .ok_or_else(|| Error::parse(Day24::DAY, 1, column(line, token),
+            format!("{:?} is not a register", token)))
+}
+
+impl std::str::FromStr for Instr {
+    type Err = Error;
+    fn from_str(line: &str) -> Result<Self> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let expect_operands = |n: usize| if tokens.len() == n + 1 { Ok(()) } else {
+            Err(Error::parse(Day24::DAY, 1, 1,
+                format!("{} takes {} operands, got {}", tokens[0], n, tokens.len() - 1)))
+        };
+        let typ = match tokens.first() {
+            Some(&"inp") => {
+                expect_operands(1)?;
+                return Ok(Instr::Inp(parse_register(line, tokens[1])?));
+            },
+            Some(&"add") => Opcode::Add,
+            Some(&"mul") => Opcode::Mul,
+            Some(&"div") => Opcode::Div,
+            Some(&"mod") => Opcode::Mod,
+            Some(&"eql") => Opcode::Eql,
+            Some(other) => return Err(Error::parse(Day24::DAY, 1, column(line, other),
+                format!("Unknown instruction {:?}", other))),
+            None => return Err(Error::parse(Day24::DAY, 1, 1, "Empty instruction")),
+        };
+        expect_operands(2)?;
+        let a = parse_register(line, tokens[1])?;
+        let b = match tokens[2].parse() {
+            Ok(n) => Operand::Num(n),
+            Err(_) => Operand::Reg(parse_register(line, tokens[2])?),
+        };
+        Ok(Instr::Binary { typ, a, b })
+    }
+}
+
+/// One instruction per line
+pub fn parse(input: &str) -> Result<Program> {
+    input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| l.trim().parse().map(|instr| (i + 1, instr)).map_err(|e: Error| e.offset_lines(i)))
+        .collect()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Input = Program;
+    type A = u64;
+    type B = u64;
+
+    fn parse(input_str: &str) -> Result<Program> {
+        parse(input_str)
+    }
+
+    fn part_a(program: &Program) -> Result<u64> {
+        solve(program, true)
+    }
+
+    fn part_b(program: &Program) -> Result<u64> {
+        solve(program, false)
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+// The puzzle has no sample MONAD, so this is a four-digit one in the same form
+pub const SAMPLE: &'static str =
+"inp w
+mul x 0
+add x z
+mod x 26
+div z 1
+add x 12
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 3
+mul y x
+add z y
+inp w
+mul x 0
+add x z
+mod x 26
+div z 1
+add x 14
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 7
+mul y x
+add z y
+inp w
+mul x 0
+add x z
+mod x 26
+div z 26
+add x -4
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 5
+mul y x
+add z y
+inp w
+mul x 0
+add x z
+mod x 26
+div z 26
+add x -11
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 9
+mul y x
+add z y";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_binary() {
+        let program = parse(
+"inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2").unwrap();
+        assert_eq!(execute(&program, &[13]).unwrap(), [1, 1, 0, 1]);
+        assert_eq!(program[3].1.to_string(), "div w 2");
+    }
+
+    #[test]
+    fn reports_crashes() {
+        let program = parse("inp x\nmul x -1\nmod x 3").unwrap();
+        assert_eq!(execute(&program, &[-2]).unwrap()[1], 2);
+        let err = execute(&program, &[2]).unwrap_err();
+        assert_eq!(err.to_string(), "day 24, line 3, column 1: mod x 3 crashes the ALU with x = -2");
+        let err = execute(&program, &[]).unwrap_err();
+        assert_eq!(err.to_string(), "day 24, line 1, column 1: Ran out of input");
+    }
+
+    #[test]
+    fn runs_long_programs() {
+        let program = parse(&"add x 1\n".repeat(200_000)).unwrap();
+        assert_eq!(execute(&program, &[]).unwrap(), [0, 200_000, 0, 0]);
+    }
+
+    #[test]
+    fn rejects_empty_and_long_monads() {
+        let err = Day24::part_a(&Day24::parse("").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "day 24: MONAD has no instructions");
+        let program = parse(&[SAMPLE; 5].join("\n")).unwrap();
+        let err = Day24::part_b(&program).unwrap_err();
+        assert_eq!(err.to_string(),
+            "day 24: Model numbers of 20 digits don't fit the answer, the most is 19");
+    }
+
+    #[test]
+    fn pairs_blocks() {
+        let program = parse(SAMPLE).unwrap();
+        let blocks = blocks(&program).unwrap();
+        assert_eq!(blocks[2], Block { div: 26, check: -4, offset: 5 });
+        assert_eq!(model_number(&blocks, true).unwrap(), [9, 6, 9, 1]);
+        assert_ne!(execute(&program, &[9, 6, 9, 2]).unwrap()[Z], 0);
+    }
+
+    aoc_common::sample_tests!(Day24, 9691, 9141);
+}
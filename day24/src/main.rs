@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day24::Day24>(day24::PUZZLE, day24::SAMPLE)
+}
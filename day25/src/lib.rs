@@ -0,0 +1,140 @@
+//! Day 25: Sea Cucumber. Two herds shuffling around a wrap-around grid.
+
+use aoc_common::grid::Grid;
+use aoc_common::{Error, Result, Solution};
+
+use std::fmt;
+
+/// What part b, which has no puzzle of its own, reports
+pub const GREETING: &'static str = "Merry Christmas!";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// The grid of `>`, `v` and `.`
+pub fn parse(input: &str) -> Result<Grid<Cell>> {
+    Grid::parse_cells(Day25::DAY, input, "sea cucumber", |b| match b {
+        b'.' => Some(Cell::Empty),
+        b'>' => Some(Cell::East),
+        b'v' => Some(Cell::South),
+        _ => None,
+    })
+}
+
+/// Moves every cucumber in `herd` one space in `dir` if that space was
+/// empty before any of them moved. Returns how many moved.
+fn move_herd(arr: &mut Grid<Cell>, herd: Cell, dir: (isize, isize)) -> usize {
+    let moves: Vec<_> = arr.iter()
+        .filter(|(_, &c)| c == herd)
+        .map(|(coord, _)| (coord, arr.wrapping_offset(coord, dir)))
+        .filter(|&(_, to)| arr[to] == Cell::Empty)
+        .collect();
+    for &(from, to) in moves.iter() {
+        arr[from] = Cell::Empty;
+        arr[to] = herd;
+    }
+    moves.len()
+}
+
+/// One step: the east-facing herd moves, then the south-facing one.
+/// Returns how many cucumbers moved.
+pub fn step(arr: &mut Grid<Cell>) -> usize {
+    move_herd(arr, Cell::East, (0, 1)) + move_herd(arr, Cell::South, (1, 0))
+}
+
+/// The first step on which no sea cucumber moves, or an error if the herds
+/// get back to where they were and so would go round forever
+pub fn part_a(arr: &mut Grid<Cell>) -> Result<usize> {
+    // Keeping a copy from each power of two steps finds a cycle within a
+    // couple of its lengths of the herds starting round it
+    let mut saved = arr.clone();
+    let mut saved_at = 0;
+    let mut ret = 1;
+    loop {
+        log::trace!("Before step {}\n{}", ret, arr);
+        if step(arr) == 0 {
+            break Ok(ret);
+        }
+        if *arr == saved {
+            break Err(Error::solve(Day25::DAY, format!(
+                "The sea cucumbers never stop: after step {} they're back where they were after step {}",
+                ret, saved_at)));
+        }
+        if ret.is_power_of_two() {
+            saved = arr.clone();
+            saved_at = ret;
+        }
+        ret += 1;
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Grid<Cell>;
+    type A = usize;
+    type B = String;
+
+    fn parse(input_str: &str) -> Result<Grid<Cell>> {
+        parse(input_str)
+    }
+
+    fn part_a(input: &Grid<Cell>) -> Result<usize> {
+        part_a(&mut input.clone())
+    }
+
+    fn part_b(_input: &Grid<Cell>) -> Result<String> {
+        Ok(GREETING.to_string())
+    }
+}
+
+pub const PUZZLE: &'static str = "";
+pub const SAMPLE: &'static str =
+"v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around() {
+        // The south-facing cucumber moves into the space the east-facing one just left
+        let mut arr = parse("...>>\n.....\n....v").unwrap();
+        assert_eq!(step(&mut arr), 2);
+        assert_eq!(arr.to_string(), ">..>v\n.....\n.....");
+    }
+
+    #[test]
+    fn stops_endless_herds() {
+        // There's always a space for one of them to move into, so they go round forever
+        let err = Day25::part_a(&parse(".>\nv.").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "day 25: \
+            The sea cucumbers never stop: after step 7 they're back where they were after step 4");
+    }
+
+    aoc_common::sample_tests!(Day25, 58, GREETING);
+}
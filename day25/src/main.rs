@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<day25::Day25>(day25::PUZZLE, day25::SAMPLE)
+}